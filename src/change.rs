@@ -0,0 +1,241 @@
+//! Changes on the system's network interfaces configuration, such as the
+//! ones reported by the interface watcher on Linux.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Addr, InterfaceFlags, MacAddr, NetworkInterface};
+
+/// A change on the system's network interfaces
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub enum InterfaceChange {
+    /// A network interface was created, alongside its addresses
+    Added(NetworkInterface),
+    /// A network interface was removed, alongside its last known addresses
+    Removed(NetworkInterface),
+    /// A network interface name changed
+    Renamed {
+        index: u32,
+        old_name: String,
+        new_name: String,
+    },
+    /// A network interface hardware address changed
+    MacChanged {
+        index: u32,
+        old: Option<MacAddr>,
+        new: Option<MacAddr>,
+    },
+    /// A network interface flags changed, e.g. when it is brought up or down
+    FlagsChanged {
+        index: u32,
+        old: InterfaceFlags,
+        new: InterfaceFlags,
+    },
+    /// An address was assigned to a network interface
+    AddressAdded { index: u32, addr: Addr },
+    /// An address was removed from a network interface
+    AddressRemoved { index: u32, addr: Addr },
+}
+
+impl InterfaceChange {
+    /// Retrieves the index of the network interface affected by this change
+    pub fn index(&self) -> u32 {
+        match self {
+            InterfaceChange::Added(network_interface)
+            | InterfaceChange::Removed(network_interface) => network_interface.index,
+            InterfaceChange::Renamed { index, .. }
+            | InterfaceChange::MacChanged { index, .. }
+            | InterfaceChange::FlagsChanged { index, .. }
+            | InterfaceChange::AddressAdded { index, .. }
+            | InterfaceChange::AddressRemoved { index, .. } => *index,
+        }
+    }
+}
+
+/// Computes the changes turning the `old` network interfaces into the `new`
+/// ones, such as two snapshots taken with `NetworkInterfaceConfig::show`.
+///
+/// Interfaces are matched by index and addresses by value, so neither the
+/// order of the interfaces nor the order of their addresses is considered a
+/// change. The remaining lifetimes of IPv6 addresses are not compared, as
+/// they decrease between snapshots.
+///
+/// ```no_run
+/// use network_interface::{diff, NetworkInterface, NetworkInterfaceConfig};
+///
+/// let old = NetworkInterface::show().unwrap();
+/// let new = NetworkInterface::show().unwrap();
+///
+/// for change in diff(&old, &new) {
+///     println!("{:?}", change);
+/// }
+/// ```
+pub fn diff(old: &[NetworkInterface], new: &[NetworkInterface]) -> Vec<InterfaceChange> {
+    let mut changes = Vec::new();
+
+    for old_interface in old {
+        if !new
+            .iter()
+            .any(|network_interface| network_interface.index == old_interface.index)
+        {
+            changes.push(InterfaceChange::Removed(old_interface.clone()));
+        }
+    }
+
+    for new_interface in new {
+        let old_interface = match old
+            .iter()
+            .find(|network_interface| network_interface.index == new_interface.index)
+        {
+            Some(old_interface) => old_interface,
+            None => {
+                changes.push(InterfaceChange::Added(new_interface.clone()));
+                continue;
+            }
+        };
+        let index = new_interface.index;
+
+        changes.extend(diff_link(old_interface, new_interface));
+
+        let contains = |addrs: &[Addr], addr: &Addr| {
+            addrs
+                .iter()
+                .any(|other| other.without_lifetimes() == addr.without_lifetimes())
+        };
+
+        for addr in &old_interface.addr {
+            if !contains(&new_interface.addr, addr) {
+                changes.push(InterfaceChange::AddressRemoved { index, addr: *addr });
+            }
+        }
+
+        for addr in &new_interface.addr {
+            if !contains(&old_interface.addr, addr) {
+                changes.push(InterfaceChange::AddressAdded { index, addr: *addr });
+            }
+        }
+    }
+
+    changes
+}
+
+/// Computes the changes on the link of a network interface, i.e. its name,
+/// hardware address and flags, between two records of the same interface
+pub(crate) fn diff_link(old: &NetworkInterface, new: &NetworkInterface) -> Vec<InterfaceChange> {
+    let index = new.index;
+    let mut changes = Vec::new();
+
+    if old.name != new.name {
+        changes.push(InterfaceChange::Renamed {
+            index,
+            old_name: old.name.clone(),
+            new_name: new.name.clone(),
+        });
+    }
+
+    if old.mac_addr != new.mac_addr {
+        changes.push(InterfaceChange::MacChanged {
+            index,
+            old: old.mac_addr,
+            new: new.mac_addr,
+        });
+    }
+
+    if old.flags != new.flags {
+        changes.push(InterfaceChange::FlagsChanged {
+            index,
+            old: old.flags,
+            new: new.flags,
+        });
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, InterfaceChange};
+    use crate::{Addr, InterfaceFlags, NetworkInterface, V4IfAddr};
+
+    fn make_eth0(ips: &[&str]) -> NetworkInterface {
+        let mut network_interface =
+            NetworkInterface::new_afinet("eth0", "192.0.2.1".parse().unwrap(), None, None, 2);
+
+        network_interface.addr = ips
+            .iter()
+            .map(|ip| {
+                Addr::V4(V4IfAddr {
+                    ip: ip.parse().unwrap(),
+                    broadcast: None,
+                    destination: None,
+                    netmask: None,
+                    label: None,
+                })
+            })
+            .collect();
+        network_interface
+    }
+
+    #[test]
+    fn ignores_order() {
+        let lo = NetworkInterface::new_afinet("lo", "127.0.0.1".parse().unwrap(), None, None, 1);
+        let old = vec![lo.clone(), make_eth0(&["192.0.2.1", "192.0.2.2"])];
+        let new = vec![make_eth0(&["192.0.2.2", "192.0.2.1"]), lo];
+
+        assert_eq!(diff(&old, &new), Vec::new());
+    }
+
+    #[test]
+    fn reports_changes() {
+        let lo = NetworkInterface::new_afinet("lo", "127.0.0.1".parse().unwrap(), None, None, 1);
+        let old = vec![lo.clone(), make_eth0(&["192.0.2.1", "192.0.2.2"])];
+        let mut eth0 = make_eth0(&["192.0.2.2", "192.0.2.3"])
+            .with_mac_addr(Some("02:fc:00:00:00:01".parse().unwrap()))
+            .with_flags(InterfaceFlags::UP);
+        let eth1 =
+            NetworkInterface::new_afinet("eth1", "192.0.2.4".parse().unwrap(), None, None, 3);
+
+        eth0.name = String::from("wan0");
+
+        let changes = diff(&old, &[eth0.clone(), eth1.clone()]);
+        let addr = |network_interface: &NetworkInterface, ip: &str| -> Addr {
+            *network_interface
+                .addr
+                .iter()
+                .find(|addr| addr.ip().to_string() == ip)
+                .unwrap()
+        };
+
+        assert_eq!(
+            changes,
+            vec![
+                InterfaceChange::Removed(lo),
+                InterfaceChange::Renamed {
+                    index: 2,
+                    old_name: String::from("eth0"),
+                    new_name: String::from("wan0"),
+                },
+                InterfaceChange::MacChanged {
+                    index: 2,
+                    old: None,
+                    new: eth0.mac_addr,
+                },
+                InterfaceChange::FlagsChanged {
+                    index: 2,
+                    old: InterfaceFlags::empty(),
+                    new: InterfaceFlags::UP,
+                },
+                InterfaceChange::AddressRemoved {
+                    index: 2,
+                    addr: addr(&old[1], "192.0.2.1"),
+                },
+                InterfaceChange::AddressAdded {
+                    index: 2,
+                    addr: addr(&eth0, "192.0.2.3"),
+                },
+                InterfaceChange::Added(eth1),
+            ]
+        );
+    }
+}
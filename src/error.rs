@@ -1,25 +1,222 @@
-use std::string::{FromUtf16Error, FromUtf8Error};
+use std::fmt::{self, Display};
+use std::io;
+use std::net::IpAddr;
+use std::string::FromUtf16Error;
 
+/// Error raised while retrieving the network interfaces or their details.
+///
+/// Errors raised by the system carry the failing operation alongside the
+/// underlying `std::io::Error`, which holds the raw OS error code, and the
+/// network interface involved if any.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("Failed to execute `{0}`. Received error code `{1}`")]
-    GetIfAddrsError(String, i32),
-    #[error("Failed to execute `{0}`. Received error code `{1}`")]
-    GetIfNameError(String, u32),
-    #[error("Failed to parse bytes into UTF-8 characters. `{0}`")]
-    ParseUtf8Error(FromUtf8Error),
-    #[error("Failed to parse bytes into UTF-16 characters. `{0}`")]
-    ParseUtf16Error(FromUtf16Error),
+    /// A function of the system failed, such as `getifaddrs` on Unix or
+    /// `GetAdaptersAddresses` on Windows
+    #[error("Failed to execute `{operation}`{}", on_interface(.interface))]
+    OsError {
+        /// The failing function
+        operation: &'static str,
+        /// The network interface involved, if any
+        interface: Option<InterfaceId>,
+        /// The error reported by the system
+        source: io::Error,
+    },
+    /// A netlink socket operation failed, or the kernel answered a request
+    /// with an error
+    #[error(
+        "Failed to execute `{operation}` on netlink socket{}",
+        on_interface(.interface)
+    )]
+    NetlinkError {
+        /// The failing socket operation, e.g. `recv`
+        operation: &'static str,
+        /// The network interface targeted by the request, if any
+        interface: Option<InterfaceId>,
+        /// The error reported by the system or the kernel
+        source: io::Error,
+    },
+    /// Entering a network namespace failed
+    #[error("Failed to enter network namespace `{path}`")]
+    SetNsError {
+        /// Path of the network namespace
+        path: String,
+        /// The error reported by the system
+        source: io::Error,
+    },
+    /// Reading a file failed, such as a sysfs attribute or a procfs table
+    #[error("Failed to read `{path}`")]
+    ReadFileError {
+        /// Path of the file
+        path: String,
+        /// The error reported by the system
+        source: io::Error,
+    },
+    #[error("Netmask `{0}` is not contiguous")]
+    NonContiguousNetmask(IpAddr),
+    #[error("Failed to parse bytes into UTF-16 characters")]
+    ParseUtf16Error(#[from] FromUtf16Error),
 }
 
-impl From<FromUtf8Error> for Error {
-    fn from(error: FromUtf8Error) -> Self {
-        Error::ParseUtf8Error(error)
+/// Network interface involved in a failed operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InterfaceId {
+    /// Interface identified by its name
+    Name(String),
+    /// Interface identified by its index
+    Index(u32),
+}
+
+impl Error {
+    /// Creates an `Error::OsError` from the last error reported by the system
+    #[cfg(not(target_os = "windows"))]
+    pub(crate) fn last_os_error(operation: &'static str) -> Self {
+        Error::OsError {
+            operation,
+            interface: None,
+            source: io::Error::last_os_error(),
+        }
+    }
+
+    /// Creates an `Error::NetlinkError` from a raw error code
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn netlink(operation: &'static str, errno: i32) -> Self {
+        Error::NetlinkError {
+            operation,
+            interface: None,
+            source: io::Error::from_raw_os_error(errno),
+        }
+    }
+
+    /// Attaches the network interface involved to this error
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn with_interface(mut self, id: InterfaceId) -> Self {
+        match &mut self {
+            Error::OsError { interface, .. } | Error::NetlinkError { interface, .. } => {
+                *interface = Some(id)
+            }
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Retrieves the error reported by the system, if any
+    pub fn io_error(&self) -> Option<&io::Error> {
+        match self {
+            Error::OsError { source, .. }
+            | Error::NetlinkError { source, .. }
+            | Error::SetNsError { source, .. }
+            | Error::ReadFileError { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the raw OS error code, such as an `errno` value on Unix
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.io_error().and_then(io::Error::raw_os_error)
+    }
+
+    /// Retrieves the failing operation, if the error was raised by the system
+    pub fn operation(&self) -> Option<&str> {
+        match self {
+            Error::OsError { operation, .. } | Error::NetlinkError { operation, .. } => {
+                Some(operation)
+            }
+            _ => None,
+        }
+    }
+
+    /// Retrieves the network interface involved, if known
+    pub fn interface(&self) -> Option<&InterfaceId> {
+        match self {
+            Error::OsError { interface, .. } | Error::NetlinkError { interface, .. } => {
+                interface.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the operation was not permitted, e.g. when entering
+    /// a network namespace without the required capabilities
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(
+            self.io_error(),
+            Some(error) if error.kind() == io::ErrorKind::PermissionDenied
+        )
+    }
+
+    /// Returns `true` if the error was raised by a netlink socket, in which
+    /// case the retrieval may be retried with another backend
+    pub fn is_netlink(&self) -> bool {
+        matches!(self, Error::NetlinkError { .. })
+    }
+}
+
+impl Display for InterfaceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterfaceId::Name(name) => write!(f, "`{}`", name),
+            InterfaceId::Index(index) => write!(f, "with index `{}`", index),
+        }
+    }
+}
+
+/// Describes the network interface involved in an error message
+fn on_interface(interface: &Option<InterfaceId>) -> String {
+    match interface {
+        Some(interface) => format!(" for interface {}", interface),
+        None => String::new(),
     }
 }
 
-impl From<FromUtf16Error> for Error {
-    fn from(error: FromUtf16Error) -> Self {
-        Error::ParseUtf16Error(error)
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+    use std::io;
+
+    use super::{Error, InterfaceId};
+
+    #[test]
+    fn describes_system_errors() {
+        let error = Error::NetlinkError {
+            operation: "sendto",
+            interface: Some(InterfaceId::Index(3)),
+            source: io::Error::from(io::ErrorKind::PermissionDenied),
+        };
+
+        assert!(error.is_permission_denied());
+        assert!(error.is_netlink());
+        assert_eq!(error.operation(), Some("sendto"));
+        assert_eq!(error.interface(), Some(&InterfaceId::Index(3)));
+        assert!(error.source().is_some());
+        assert_eq!(
+            error.to_string(),
+            "Failed to execute `sendto` on netlink socket for interface with index `3`"
+        );
+
+        let error = Error::ReadFileError {
+            path: String::from("/proc/net/route"),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        };
+
+        assert!(!error.is_permission_denied());
+        assert_eq!(error.operation(), None);
+        assert_eq!(error.raw_os_error(), None);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    fn keeps_raw_os_error() {
+        let error = Error::netlink("bind", libc::EACCES)
+            .with_interface(InterfaceId::Name(String::from("eth0")));
+
+        assert!(error.is_permission_denied());
+        assert_eq!(error.raw_os_error(), Some(libc::EACCES));
+        assert_eq!(
+            error.interface(),
+            Some(&InterfaceId::Name(String::from("eth0")))
+        );
     }
 }
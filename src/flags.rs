@@ -0,0 +1,270 @@
+//! Platform independent representation of the flags describing the state and
+//! capabilities of a network interface, such as the `IFF_*` flags on Unix,
+//! and of the flags describing the state of an IPv6 address.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Implements a set of flags stored as raw bits in a tuple struct, declaring
+/// the provided flags alongside the operations shared by every set.
+///
+/// Flags are listed by their name in the `Debug` output.
+macro_rules! bitset {
+    (
+        $name:ident($bits:ty) {
+            $(
+                $(#[$meta:meta])*
+                const $flag:ident = $value:expr;
+            )*
+        }
+    ) => {
+        impl $name {
+            $(
+                $(#[$meta])*
+                pub const $flag: $name = $name($value);
+            )*
+
+            const NAMES: &'static [($name, &'static str)] = &[$(($name::$flag, stringify!($flag))),*];
+
+            /// Creates an empty set of flags
+            pub const fn empty() -> Self {
+                $name(0)
+            }
+
+            /// Retrieves the raw bits for this set of flags
+            pub const fn bits(&self) -> $bits {
+                self.0
+            }
+
+            /// Returns `true` if no flag is set
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns `true` if every flag in `other` is set
+            pub const fn contains(&self, other: $name) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Returns `true` if any flag in `other` is set
+            pub const fn intersects(&self, other: $name) -> bool {
+                self.0 & other.0 != 0
+            }
+
+            /// Sets every flag in `other`
+            pub fn insert(&mut self, other: $name) {
+                self.0 |= other.0;
+            }
+
+            /// Unsets every flag in `other`
+            pub fn remove(&mut self, other: $name) {
+                self.0 &= !other.0;
+            }
+
+            /// Sets or unsets every flag in `other` based on `value`
+            pub fn set(&mut self, other: $name, value: bool) {
+                if value {
+                    self.insert(other);
+                } else {
+                    self.remove(other);
+                }
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = $name;
+
+            fn bitor(self, rhs: $name) -> $name {
+                $name(self.0 | rhs.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: $name) {
+                self.insert(rhs);
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = $name;
+
+            fn bitand(self, rhs: $name) -> $name {
+                $name(self.0 & rhs.0)
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}(", stringify!($name))?;
+
+                let mut names = $name::NAMES
+                    .iter()
+                    .filter(|(flag, _)| self.contains(*flag))
+                    .map(|(_, name)| name);
+
+                if let Some(name) = names.next() {
+                    write!(f, "{}", name)?;
+                }
+
+                for name in names {
+                    write!(f, " | {}", name)?;
+                }
+
+                write!(f, ")")
+            }
+        }
+    };
+}
+
+pub(crate) use bitset;
+
+/// A set of flags for a network interface
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct InterfaceFlags(u32);
+
+bitset! {
+    InterfaceFlags(u32) {
+        /// Interface is administratively up
+        const UP = 1 << 0;
+        /// Interface supports broadcast and has a valid broadcast address
+        const BROADCAST = 1 << 1;
+        /// Interface is a loopback
+        const LOOPBACK = 1 << 2;
+        /// Interface is a point-to-point link
+        const POINT_TO_POINT = 1 << 3;
+        /// Interface resources are allocated and it is operational
+        const RUNNING = 1 << 4;
+        /// Interface does not use an address resolution protocol
+        const NOARP = 1 << 5;
+        /// Interface receives every packet on the link
+        const PROMISC = 1 << 6;
+        /// Interface supports multicast
+        const MULTICAST = 1 << 7;
+    }
+}
+
+impl InterfaceFlags {
+    /// Returns `true` if the interface is administratively up
+    pub const fn is_up(&self) -> bool {
+        self.contains(InterfaceFlags::UP)
+    }
+
+    /// Returns `true` if the interface is operational
+    pub const fn is_running(&self) -> bool {
+        self.contains(InterfaceFlags::RUNNING)
+    }
+
+    /// Returns `true` if the interface is a loopback
+    pub const fn is_loopback(&self) -> bool {
+        self.contains(InterfaceFlags::LOOPBACK)
+    }
+
+    /// Returns `true` if the interface supports broadcast
+    pub const fn is_broadcast(&self) -> bool {
+        self.contains(InterfaceFlags::BROADCAST)
+    }
+
+    /// Returns `true` if the interface supports multicast
+    pub const fn is_multicast(&self) -> bool {
+        self.contains(InterfaceFlags::MULTICAST)
+    }
+
+    /// Returns `true` if the interface is a point-to-point link
+    pub const fn is_point_to_point(&self) -> bool {
+        self.contains(InterfaceFlags::POINT_TO_POINT)
+    }
+}
+
+/// A set of flags for an IPv6 address, as the `IFA_F_*` flags on Linux
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man8/ip-address.8.html>
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct AddrFlags(u32);
+
+bitset! {
+    AddrFlags(u32) {
+        /// Temporary address generated for privacy extensions (RFC 4941)
+        const TEMPORARY = 0x01;
+        /// Duplicate address detection is disabled for the address
+        const NODAD = 0x02;
+        /// Address usable while duplicate address detection is still running
+        /// (RFC 4429)
+        const OPTIMISTIC = 0x04;
+        /// Duplicate address detection failed, the address is not usable
+        const DADFAILED = 0x08;
+        /// Preferred lifetime expired, the address should not be used for new
+        /// connections
+        const DEPRECATED = 0x20;
+        /// Duplicate address detection is running, the address is not usable yet
+        const TENTATIVE = 0x40;
+        /// Address configured statically rather than by autoconfiguration
+        const PERMANENT = 0x80;
+        /// Temporary addresses are generated from the prefix of the address
+        const MANAGETEMPADDR = 0x100;
+        /// No route is created for the prefix of the address
+        const NOPREFIXROUTE = 0x200;
+    }
+}
+
+impl AddrFlags {
+    /// Creates a set of flags from the raw `IFA_F_*` bits
+    pub const fn from_bits(bits: u32) -> Self {
+        AddrFlags(bits)
+    }
+
+    /// Returns `true` if duplicate address detection is still running
+    pub const fn is_tentative(&self) -> bool {
+        self.contains(AddrFlags::TENTATIVE)
+    }
+
+    /// Returns `true` if the preferred lifetime of the address expired
+    pub const fn is_deprecated(&self) -> bool {
+        self.contains(AddrFlags::DEPRECATED)
+    }
+
+    /// Returns `true` if the address is a privacy extensions address
+    pub const fn is_temporary(&self) -> bool {
+        self.contains(AddrFlags::TEMPORARY)
+    }
+
+    /// Returns `true` if duplicate address detection failed
+    pub const fn is_dad_failed(&self) -> bool {
+        self.contains(AddrFlags::DADFAILED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddrFlags, InterfaceFlags};
+
+    #[test]
+    fn sets_and_checks_flags() {
+        let mut flags = InterfaceFlags::UP | InterfaceFlags::LOOPBACK;
+
+        assert!(flags.is_up());
+        assert!(flags.is_loopback());
+        assert!(!flags.is_running());
+
+        flags.set(InterfaceFlags::RUNNING, true);
+        flags.remove(InterfaceFlags::LOOPBACK);
+
+        assert!(flags.is_running());
+        assert!(!flags.is_loopback());
+        assert_eq!(format!("{:?}", flags), "InterfaceFlags(UP | RUNNING)");
+        assert_eq!(format!("{:?}", InterfaceFlags::empty()), "InterfaceFlags()");
+    }
+
+    #[test]
+    fn checks_address_flags() {
+        let flags = AddrFlags::from_bits(0x40 | 0x80);
+
+        assert!(flags.is_tentative());
+        assert!(!flags.is_deprecated());
+        assert!(flags.intersects(AddrFlags::DEPRECATED | AddrFlags::TENTATIVE));
+        assert!(!flags.intersects(AddrFlags::TEMPORARY | AddrFlags::DADFAILED));
+        assert_eq!(format!("{:?}", flags), "AddrFlags(TENTATIVE | PERMANENT)");
+    }
+}
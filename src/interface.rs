@@ -1,10 +1,14 @@
 //! Network Interface abstraction from commonly used fields for nodes from the
 //! linked list provided by system functions like `getifaddrs` and
 //! `GetAdaptersAddresses`.
+use std::ffi::OsString;
 use std::fmt::Debug;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV6};
+use std::time::Duration;
+
+use crate::{AddrFlags, AddrLabel, InterfaceFlags, InterfaceKind, LinkDetails, MacAddr};
 
 /// An alias for an `Option` that wraps either a `Ipv4Addr` or a `Ipv6Addr`
 /// representing the IP for a Network Interface netmask
@@ -13,15 +17,26 @@ pub type Netmask<T> = Option<T>;
 /// A system's network interface
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct NetworkInterface {
-    /// Interface's name
+    /// Interface's name, where the bytes which are not valid UTF-8 are
+    /// replaced by `U+FFFD`
     pub name: String,
+    /// Interface's name exactly as provided by the system, which may not be
+    /// valid UTF-8 on Unix
+    pub os_name: OsString,
     /// Interface's address
     pub addr: Vec<Addr>,
     /// MAC Address
-    pub mac_addr: Option<String>,
+    pub mac_addr: Option<MacAddr>,
     /// Interface's index
     pub index: u32,
+    /// Interface's flags
+    pub flags: InterfaceFlags,
+    /// Kind of the device behind the interface
+    pub kind: InterfaceKind,
+    /// Link layer details of the interface
+    pub link: LinkDetails,
 }
 
 /// Network interface address
@@ -37,25 +52,76 @@ pub enum Addr {
 /// IPV4 Interface from the AFINET network interface family
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct V4IfAddr {
     /// The IP address for this network interface
     pub ip: Ipv4Addr,
     /// The broadcast address for this interface
     pub broadcast: Option<Ipv4Addr>,
+    /// The address of the peer for this interface when it is a point-to-point
+    /// link
+    pub destination: Option<Ipv4Addr>,
     /// The netmask for this interface
     pub netmask: Netmask<Ipv4Addr>,
+    /// The label of this address, such as `eth0:1` for an alias of `eth0`.
+    ///
+    /// `None` for the addresses labeled after their interface, which is the
+    /// default on Linux, and on other platforms which provide no label.
+    pub label: Option<AddrLabel>,
 }
 
 /// IPV6 Interface from the AFINET6 network interface family
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct V6IfAddr {
     /// The IP address for this network interface
     pub ip: Ipv6Addr,
     /// The broadcast address for this interface
     pub broadcast: Option<Ipv6Addr>,
+    /// The address of the peer for this interface when it is a point-to-point
+    /// link
+    pub destination: Option<Ipv6Addr>,
     /// The netmask for this interface
     pub netmask: Netmask<Ipv6Addr>,
+    /// The scope (zone) identifier for this address, which is the index of
+    /// the interface for link-local addresses and `0` for addresses that are
+    /// not scoped
+    pub scope_id: u32,
+    /// The flags of this address, such as whether duplicate address detection
+    /// is still running.
+    ///
+    /// `None` when not reported by the system, as on platforms other than
+    /// Linux or with the `getifaddrs` backend.
+    pub flags: Option<AddrFlags>,
+    /// The lifetimes of this address, `None` when not reported by the system
+    /// as for `flags`
+    pub lifetimes: Option<AddrLifetimes>,
+}
+
+/// Remaining lifetimes of an IPv6 address, as configured statically or by
+/// stateless address autoconfiguration (RFC 4862)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct AddrLifetimes {
+    /// Time left before the address is deprecated, `None` if it never is
+    pub preferred: Option<Duration>,
+    /// Time left before the address is removed, `None` if it never is
+    pub valid: Option<Duration>,
+}
+
+/// Scope of an IP address, sorted from the narrowest to the widest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum AddrScope {
+    /// Valid only within the host, e.g. loopback addresses
+    Host,
+    /// Valid only within the link, e.g. link-local addresses
+    Link,
+    /// Valid only within the site, e.g. deprecated IPv6 site-local addresses
+    Site,
+    /// Valid everywhere
+    Global,
 }
 
 impl NetworkInterface {
@@ -66,17 +132,17 @@ impl NetworkInterface {
         broadcast: Option<Ipv4Addr>,
         index: u32,
     ) -> NetworkInterface {
-        let ifaddr_v4 = V4IfAddr {
-            ip: addr,
-            broadcast,
-            netmask,
-        };
+        let ifaddr_v4 = V4IfAddr::new(addr, netmask, broadcast);
 
         NetworkInterface {
             name: name.to_string(),
+            os_name: OsString::from(name),
             addr: vec![Addr::V4(ifaddr_v4)],
             mac_addr: None,
             index,
+            flags: InterfaceFlags::empty(),
+            kind: InterfaceKind::Unknown,
+            link: LinkDetails::default(),
         }
     }
 
@@ -87,23 +153,70 @@ impl NetworkInterface {
         broadcast: Option<Ipv6Addr>,
         index: u32,
     ) -> NetworkInterface {
-        let ifaddr_v6 = V6IfAddr {
-            ip: addr,
-            broadcast,
-            netmask,
-        };
+        let ifaddr_v6 = V6IfAddr::new(addr, netmask, broadcast);
 
         NetworkInterface {
             name: name.to_string(),
+            os_name: OsString::from(name),
             addr: vec![Addr::V6(ifaddr_v6)],
             mac_addr: None,
             index,
+            flags: InterfaceFlags::empty(),
+            kind: InterfaceKind::Unknown,
+            link: LinkDetails::default(),
         }
     }
 
-    pub fn with_mac_addr(self, mac_addr: Option<String>) -> Self {
+    pub fn with_mac_addr(self, mac_addr: Option<MacAddr>) -> Self {
         Self { mac_addr, ..self }
     }
+
+    pub fn with_flags(self, flags: InterfaceFlags) -> Self {
+        Self { flags, ..self }
+    }
+
+    pub fn with_kind(self, kind: InterfaceKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// Returns `true` if the interface is administratively up
+    pub fn is_up(&self) -> bool {
+        self.flags.is_up()
+    }
+
+    /// Returns `true` if the interface is operational
+    pub fn is_running(&self) -> bool {
+        self.flags.is_running()
+    }
+
+    /// Returns `true` if the interface is operationally up, that is able to
+    /// transfer data, unlike an interface administratively up with its cable
+    /// unplugged.
+    ///
+    /// Relies on `LinkDetails::operstate` when reported, and on the
+    /// `RUNNING` flag otherwise. `OperState::Unknown` is considered up, see
+    /// `OperState::is_up`.
+    pub fn is_oper_up(&self) -> bool {
+        match self.link.operstate {
+            Some(operstate) => self.is_up() && operstate.is_up(),
+            None => self.is_up() && self.is_running(),
+        }
+    }
+
+    /// Returns `true` if the interface is a loopback
+    pub fn is_loopback(&self) -> bool {
+        self.flags.is_loopback()
+    }
+
+    /// Returns `true` if the interface supports multicast
+    pub fn is_multicast(&self) -> bool {
+        self.flags.is_multicast()
+    }
+
+    /// Returns `true` if the interface is a point-to-point link
+    pub fn is_point_to_point(&self) -> bool {
+        self.flags.is_point_to_point()
+    }
 }
 
 impl Addr {
@@ -121,10 +234,246 @@ impl Addr {
         }
     }
 
+    pub fn destination(self) -> Option<IpAddr> {
+        match self {
+            Addr::V4(ifaddr_v4) => ifaddr_v4.destination.map(Into::into),
+            Addr::V6(ifaddr_v6) => ifaddr_v6.destination.map(Into::into),
+        }
+    }
+
     pub fn netmask(self) -> Netmask<IpAddr> {
         match self {
             Addr::V4(ifaddr_v4) => ifaddr_v4.netmask.map(Into::into),
             Addr::V6(ifaddr_v6) => ifaddr_v6.netmask.map(Into::into),
         }
     }
+
+    pub fn scope(self) -> AddrScope {
+        match self {
+            Addr::V4(ifaddr_v4) => ifaddr_v4.scope(),
+            Addr::V6(ifaddr_v6) => ifaddr_v6.scope(),
+        }
+    }
+
+    /// Clears the remaining lifetimes of an IPv6 address, which decrease
+    /// over time, so that two reports of the same address compare equal
+    pub(crate) fn without_lifetimes(self) -> Addr {
+        match self {
+            Addr::V4(ifaddr_v4) => Addr::V4(ifaddr_v4),
+            Addr::V6(ifaddr_v6) => Addr::V6(V6IfAddr {
+                lifetimes: None,
+                ..ifaddr_v6
+            }),
+        }
+    }
+}
+
+impl V4IfAddr {
+    /// Creates an IPv4 address without destination nor label, which can be
+    /// set with the `with_*` methods.
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use network_interface::{AddrLabel, V4IfAddr};
+    ///
+    /// let ifaddr_v4 = V4IfAddr::new(
+    ///     Ipv4Addr::new(192, 0, 2, 1),
+    ///     Some(Ipv4Addr::new(255, 255, 255, 0)),
+    ///     Some(Ipv4Addr::new(192, 0, 2, 255)),
+    /// )
+    /// .with_label(AddrLabel::new("eth0:1"));
+    ///
+    /// assert_eq!(ifaddr_v4.label.as_deref(), Some("eth0:1"));
+    /// ```
+    pub fn new(ip: Ipv4Addr, netmask: Netmask<Ipv4Addr>, broadcast: Option<Ipv4Addr>) -> Self {
+        V4IfAddr {
+            ip,
+            broadcast,
+            destination: None,
+            netmask,
+            label: None,
+        }
+    }
+
+    pub fn with_destination(self, destination: Option<Ipv4Addr>) -> Self {
+        Self {
+            destination,
+            ..self
+        }
+    }
+
+    pub fn with_label(self, label: Option<AddrLabel>) -> Self {
+        Self { label, ..self }
+    }
+
+    /// Classifies the scope of this address
+    pub fn scope(&self) -> AddrScope {
+        let octets = self.ip.octets();
+
+        if self.ip.is_loopback() {
+            AddrScope::Host
+        } else if self.ip.is_link_local() || octets[..3] == [224, 0, 0] {
+            AddrScope::Link
+        } else {
+            AddrScope::Global
+        }
+    }
+}
+
+impl V6IfAddr {
+    /// Creates an IPv6 address which is not scoped and without destination,
+    /// flags nor lifetimes, which can be set with the `with_*` methods
+    pub fn new(ip: Ipv6Addr, netmask: Netmask<Ipv6Addr>, broadcast: Option<Ipv6Addr>) -> Self {
+        V6IfAddr {
+            ip,
+            broadcast,
+            destination: None,
+            netmask,
+            scope_id: 0,
+            flags: None,
+            lifetimes: None,
+        }
+    }
+
+    pub fn with_destination(self, destination: Option<Ipv6Addr>) -> Self {
+        Self {
+            destination,
+            ..self
+        }
+    }
+
+    pub fn with_scope_id(self, scope_id: u32) -> Self {
+        Self { scope_id, ..self }
+    }
+
+    pub fn with_flags(self, flags: Option<AddrFlags>) -> Self {
+        Self { flags, ..self }
+    }
+
+    pub fn with_lifetimes(self, lifetimes: Option<AddrLifetimes>) -> Self {
+        Self { lifetimes, ..self }
+    }
+
+    /// Classifies the scope of this address
+    pub fn scope(&self) -> AddrScope {
+        ipv6_scope(&self.ip)
+    }
+
+    /// Creates a `SocketAddrV6` for this address and the provided port,
+    /// preserving the scope identifier required by link-local addresses
+    pub fn socket_addr(&self, port: u16) -> SocketAddrV6 {
+        SocketAddrV6::new(self.ip, port, 0, self.scope_id)
+    }
+
+    /// Returns `true` if the address can be used for new connections, that
+    /// is it is neither tentative, deprecated nor failed duplicate address
+    /// detection.
+    ///
+    /// Addresses are assumed to be preferred when their flags are not
+    /// reported by the system.
+    pub fn is_preferred(&self) -> bool {
+        let unusable = AddrFlags::TENTATIVE | AddrFlags::DEPRECATED | AddrFlags::DADFAILED;
+
+        !matches!(self.flags, Some(flags) if flags.intersects(unusable))
+    }
+}
+
+/// Classifies the scope of an IPv6 address based on its prefix, or on the
+/// scope field for multicast addresses.
+///
+/// ## References
+///
+/// <https://www.rfc-editor.org/rfc/rfc4291#section-2.4>
+pub(crate) fn ipv6_scope(ip: &Ipv6Addr) -> AddrScope {
+    let segments = ip.segments();
+
+    if ip.is_loopback() {
+        return AddrScope::Host;
+    }
+
+    if ip.is_multicast() {
+        return match segments[0] & 0x000f {
+            0x1 => AddrScope::Host,
+            0x2 => AddrScope::Link,
+            0x3..=0x5 => AddrScope::Site,
+            _ => AddrScope::Global,
+        };
+    }
+
+    match segments[0] & 0xffc0 {
+        0xfe80 => AddrScope::Link,
+        0xfec0 => AddrScope::Site,
+        _ => AddrScope::Global,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::{ipv6_scope, AddrScope, NetworkInterface, V4IfAddr};
+    use crate::{InterfaceFlags, OperState};
+
+    #[test]
+    fn classifies_address_scopes() {
+        let ifaddr_v4 = |ip: Ipv4Addr| V4IfAddr {
+            ip,
+            broadcast: None,
+            destination: None,
+            netmask: None,
+            label: None,
+        };
+
+        assert_eq!(ifaddr_v4(Ipv4Addr::LOCALHOST).scope(), AddrScope::Host);
+        assert_eq!(
+            ifaddr_v4(Ipv4Addr::new(169, 254, 1, 1)).scope(),
+            AddrScope::Link
+        );
+        assert_eq!(
+            ifaddr_v4(Ipv4Addr::new(192, 168, 1, 1)).scope(),
+            AddrScope::Global
+        );
+
+        for (ip, scope) in [
+            ("::1", AddrScope::Host),
+            ("fe80::1", AddrScope::Link),
+            ("febf::1", AddrScope::Link),
+            ("fec0::1", AddrScope::Site),
+            ("fd00::1", AddrScope::Global),
+            ("2001:db8::1", AddrScope::Global),
+            ("ff02::1", AddrScope::Link),
+            ("ff05::2", AddrScope::Site),
+            ("ff0e::1", AddrScope::Global),
+        ] {
+            assert_eq!(
+                ipv6_scope(&ip.parse::<Ipv6Addr>().unwrap()),
+                scope,
+                "{}",
+                ip
+            );
+        }
+    }
+
+    #[test]
+    fn checks_operational_state() {
+        let with_operstate = |flags, operstate| {
+            let mut network_interface =
+                NetworkInterface::new_afinet("eth0", Ipv4Addr::new(192, 0, 2, 1), None, None, 2)
+                    .with_flags(flags);
+
+            network_interface.link.operstate = operstate;
+            network_interface
+        };
+        let up = InterfaceFlags::UP;
+        let running = InterfaceFlags::UP | InterfaceFlags::RUNNING;
+
+        assert!(with_operstate(up, Some(OperState::Up)).is_oper_up());
+        // reported by the devices not tracking their state, such as `lo`
+        assert!(with_operstate(up, Some(OperState::Unknown)).is_oper_up());
+        assert!(!with_operstate(running, Some(OperState::LowerLayerDown)).is_oper_up());
+        assert!(!with_operstate(InterfaceFlags::empty(), Some(OperState::Up)).is_oper_up());
+        assert!(with_operstate(running, None).is_oper_up());
+        assert!(!with_operstate(up, None).is_oper_up());
+    }
 }
@@ -0,0 +1,115 @@
+//! Kind of the device behind a network interface, telling apart physical
+//! network adapters from the virtual devices such as bridges or veths.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of the device behind a network interface
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man8/ip-link.8.html>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub enum InterfaceKind {
+    /// The kind could not be determined, as on platforms other than Linux
+    Unknown,
+    /// Loopback interface
+    Loopback,
+    /// Network adapter not created by a virtual device driver, such as an
+    /// Ethernet or wireless adapter
+    Physical,
+    /// Ethernet bridge
+    Bridge,
+    /// VLAN on top of another interface
+    Vlan {
+        /// VLAN identifier
+        id: u16,
+        /// Tag protocol
+        protocol: VlanProtocol,
+    },
+    /// Virtual Ethernet pair end
+    Veth,
+    /// Bonding of several interfaces
+    Bond,
+    /// Layer 3 TUN device
+    Tun,
+    /// Layer 2 TAP device
+    Tap,
+    /// Dummy interface
+    Dummy,
+    /// MACVLAN on top of another interface
+    Macvlan {
+        /// Forwarding mode between the MACVLANs of the same interface
+        mode: MacvlanMode,
+    },
+    /// WireGuard tunnel
+    Wireguard,
+    /// Other virtual device, identified by its driver kind, e.g. `vxlan`
+    Other(String),
+}
+
+/// Tag protocol of a VLAN
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum VlanProtocol {
+    /// IEEE 802.1Q tag, the customer VLAN tag
+    Ieee8021Q,
+    /// IEEE 802.1ad tag, the service VLAN tag used for QinQ
+    Ieee8021Ad,
+    /// Other protocol, identified by its EtherType
+    Other(u16),
+}
+
+/// Forwarding mode of a MACVLAN
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum MacvlanMode {
+    /// No communication with the other MACVLANs
+    Private,
+    /// Communication with the other MACVLANs through the external switch
+    Vepa,
+    /// Direct communication with the other MACVLANs
+    Bridge,
+    /// Single MACVLAN taking over the underlying interface
+    Passthru,
+    /// Only receives from an allowed list of source addresses
+    Source,
+    /// Other mode, identified by its `MACVLAN_MODE_*` number
+    Other(u32),
+}
+
+impl InterfaceKind {
+    /// Returns `true` if the interface is backed by a virtual device driver
+    pub fn is_virtual(&self) -> bool {
+        !matches!(
+            self,
+            InterfaceKind::Unknown | InterfaceKind::Loopback | InterfaceKind::Physical
+        )
+    }
+}
+
+impl From<u16> for VlanProtocol {
+    /// Maps an EtherType to a `VlanProtocol`
+    fn from(protocol: u16) -> Self {
+        match protocol {
+            0x8100 => VlanProtocol::Ieee8021Q,
+            0x88a8 => VlanProtocol::Ieee8021Ad,
+            protocol => VlanProtocol::Other(protocol),
+        }
+    }
+}
+
+impl From<u32> for MacvlanMode {
+    /// Maps the `MACVLAN_MODE_*` numbers to a `MacvlanMode`
+    fn from(mode: u32) -> Self {
+        match mode {
+            1 => MacvlanMode::Private,
+            2 => MacvlanMode::Vepa,
+            4 => MacvlanMode::Bridge,
+            8 => MacvlanMode::Passthru,
+            16 => MacvlanMode::Source,
+            mode => MacvlanMode::Other(mode),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Label of an IPv4 address, used on Linux to name the aliases of an
+//! interface such as `eth0:1`.
+use std::fmt::{self, Debug, Display};
+use std::ops::Deref;
+
+/// Label of an IPv4 address.
+///
+/// Labels are limited to the length of interface names, so they are stored
+/// inline and `V4IfAddr` remains `Copy`. Labels which are not valid UTF-8 are
+/// decoded lossily, as for the interface names.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AddrLabel {
+    bytes: [u8; AddrLabel::CAPACITY],
+    len: u8,
+}
+
+impl AddrLabel {
+    /// Maximum length in bytes of a label, as `IFNAMSIZ` minus the trailing
+    /// NUL character
+    pub const MAX_LEN: usize = 15;
+
+    /// Length in bytes of a label decoded lossily, where each byte which is
+    /// not valid UTF-8 is replaced by the three bytes of `U+FFFD`
+    const CAPACITY: usize = 3 * AddrLabel::MAX_LEN;
+
+    /// Creates an `AddrLabel` from a string.
+    ///
+    /// Returns `None` if `label` is empty, longer than `AddrLabel::MAX_LEN`
+    /// or holds a NUL character.
+    pub fn new(label: &str) -> Option<Self> {
+        if label.len() > AddrLabel::MAX_LEN {
+            return None;
+        }
+
+        AddrLabel::store(label)
+    }
+
+    /// Creates an `AddrLabel` from the bytes reported by the system, which
+    /// are not required to be valid UTF-8
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > AddrLabel::MAX_LEN {
+            return None;
+        }
+
+        AddrLabel::store(&String::from_utf8_lossy(bytes))
+    }
+
+    fn store(label: &str) -> Option<Self> {
+        if label.is_empty() || label.len() > AddrLabel::CAPACITY || label.contains('\0') {
+            return None;
+        }
+
+        let mut addr_label = AddrLabel {
+            bytes: [0; AddrLabel::CAPACITY],
+            len: label.len() as u8,
+        };
+
+        addr_label.bytes[..label.len()].copy_from_slice(label.as_bytes());
+
+        Some(addr_label)
+    }
+
+    /// Retrieves this label as a string slice
+    pub fn as_str(&self) -> &str {
+        // only built from string slices
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+impl Deref for AddrLabel {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for AddrLabel {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for AddrLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Debug for AddrLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AddrLabel({:?})", self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AddrLabel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AddrLabel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let string = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        // labels decoded lossily may be longer than `AddrLabel::MAX_LEN`
+        AddrLabel::store(&string).ok_or_else(|| serde::de::Error::custom("invalid address label"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddrLabel;
+
+    #[test]
+    fn validates_labels() {
+        let label = AddrLabel::new("eth0:1").unwrap();
+
+        assert_eq!(label.as_str(), "eth0:1");
+        assert_eq!(label.to_string(), "eth0:1");
+        assert_eq!(format!("{:?}", label), "AddrLabel(\"eth0:1\")");
+        assert!(AddrLabel::new("eth0:123456789a").is_some());
+        assert!(AddrLabel::new("eth0:123456789ab").is_none());
+        assert!(AddrLabel::new("").is_none());
+        assert!(AddrLabel::new("eth0\0").is_none());
+    }
+
+    #[test]
+    fn decodes_labels_lossily() {
+        let label = AddrLabel::from_bytes(b"\xff\xff\xff\xff\xff:123456789").unwrap();
+
+        assert_eq!(
+            label.as_str(),
+            "\u{fffd}\u{fffd}\u{fffd}\u{fffd}\u{fffd}:123456789"
+        );
+        assert!(AddrLabel::from_bytes(b"eth0:1\xff").is_some());
+        assert!(AddrLabel::from_bytes(b"eth0:123456789ab").is_none());
+    }
+}
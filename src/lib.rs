@@ -1,16 +1,107 @@
+use std::net::IpAddr;
+
+mod change;
 mod error;
+mod flags;
 mod interface;
+mod kind;
+mod label;
+mod link;
+mod mac_addr;
+mod neighbor;
+mod provider;
+mod query;
+mod route;
+mod stats;
+mod subnet;
 mod target;
 mod test;
+#[cfg(feature = "tokio")]
+mod tokio;
 
 mod utils;
 
+pub use change::{diff, InterfaceChange};
 pub use error::*;
+pub use flags::*;
 pub use interface::*;
+pub use kind::{InterfaceKind, MacvlanMode, VlanProtocol};
+pub use label::AddrLabel;
+pub use link::{AddrAssignType, Duplex, LinkDetails, LinkMode, OperState};
+pub use mac_addr::*;
+pub use neighbor::{Neighbor, NeighborFlags, NeighborState};
+#[cfg(feature = "test-util")]
+pub use provider::FakeProvider;
+pub use provider::{InterfaceProvider, SystemProvider};
+pub use query::{Family, InterfaceQuery};
+pub use route::*;
+pub use stats::InterfaceStats;
+pub use subnet::{Hosts, ParseIfAddrError};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use target::{Backend, Sysfs, Watcher};
+
+#[cfg(all(feature = "tokio", any(target_os = "android", target_os = "linux")))]
+pub use crate::tokio::InterfaceChangeStream;
+
+/// Retrieves the entries of the IPv4 (ARP) and IPv6 (NDP) neighbor tables
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn neighbors() -> Result<Vec<Neighbor>> {
+    target::neighbors()
+}
+
+/// Retrieves the routes of every routing table of the system
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn routes() -> Result<Vec<Route>> {
+    target::routes()
+}
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
 pub trait NetworkInterfaceConfig {
     /// List system's network interfaces configuration
+    ///
+    /// Network interfaces are sorted by index, each one holding every
+    /// address assigned to the interface with such index.
     fn show() -> Result<Vec<interface::NetworkInterface>>;
+
+    /// Retrieves the network interface with the provided name
+    ///
+    /// Unless overridden by the platform, every network interface is
+    /// retrieved with `show` and filtered afterwards.
+    fn by_name(name: &str) -> Result<Option<interface::NetworkInterface>> {
+        Ok(Self::show()?
+            .into_iter()
+            .find(|network_interface| network_interface.name == name))
+    }
+
+    /// Retrieves the network interface with the provided index
+    ///
+    /// Unless overridden by the platform, every network interface is
+    /// retrieved with `show` and filtered afterwards.
+    fn by_index(index: u32) -> Result<Option<interface::NetworkInterface>> {
+        Ok(Self::show()?
+            .into_iter()
+            .find(|network_interface| network_interface.index == index))
+    }
+
+    /// Retrieves the network interface with the provided hardware address
+    ///
+    /// Unless overridden by the platform, every network interface is
+    /// retrieved with `show` and filtered afterwards.
+    fn by_mac(mac_addr: &MacAddr) -> Result<Option<interface::NetworkInterface>> {
+        Ok(Self::show()?
+            .into_iter()
+            .find(|network_interface| network_interface.mac_addr.as_ref() == Some(mac_addr)))
+    }
+
+    /// Retrieves the network interface holding the provided address
+    ///
+    /// Unless overridden by the platform, every network interface is
+    /// retrieved with `show` and filtered afterwards.
+    fn by_ip(ip: IpAddr) -> Result<Option<interface::NetworkInterface>> {
+        Ok(Self::show()?
+            .into_iter()
+            .find(|network_interface| network_interface.addr.iter().any(|addr| addr.ip() == ip)))
+    }
 }
@@ -0,0 +1,158 @@
+//! Link layer details of a network interface, such as its operational state
+//! or the speed negotiated by the network adapter.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Link layer details of a network interface, every detail is `None` when
+/// it is not provided by the system for the interface.
+///
+/// On Linux, the details missing from netlink and `getifaddrs`, such as the
+/// speed, the duplex mode and the origin of the hardware address, are only
+/// exposed on sysfs and are not read unless asked for with `Sysfs::enrich`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub struct LinkDetails {
+    /// Hardware type of the interface, as one of the `ARPHRD_*` numbers on
+    /// Linux, e.g. `1` for Ethernet
+    pub link_type: Option<u16>,
+    /// Maximum transmission unit in bytes
+    pub mtu: Option<u32>,
+    /// Operational state of the interface
+    pub operstate: Option<OperState>,
+    /// Whether the physical link is up, e.g. a cable is plugged
+    pub carrier: Option<bool>,
+    /// Number of times the physical link went up
+    pub carrier_up_count: Option<u32>,
+    /// Number of times the physical link went down
+    pub carrier_down_count: Option<u32>,
+    /// Policy used to compute the operational state of the interface
+    pub link_mode: Option<LinkMode>,
+    /// Negotiated speed in Mbit/s
+    pub speed: Option<u32>,
+    /// Negotiated duplex mode
+    pub duplex: Option<Duplex>,
+    /// Origin of the hardware address of the interface
+    pub addr_assign_type: Option<AddrAssignType>,
+    /// Name of the bus device backing the interface, such as the PCI address
+    /// `0000:00:03.0`, or `None` for virtual devices
+    pub device: Option<String>,
+}
+
+/// Operational state of a network interface, as defined by RFC 2863
+///
+/// ## References
+///
+/// <https://www.kernel.org/doc/html/latest/networking/operstates.html>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum OperState {
+    /// The state could not be determined, as for most virtual devices
+    Unknown,
+    /// A component of the interface is missing
+    NotPresent,
+    /// The interface is unable to transfer data
+    Down,
+    /// The interface is down as the interface it is stacked on is down
+    LowerLayerDown,
+    /// The interface is in test mode
+    Testing,
+    /// The interface is up but waiting for an external event, e.g. wireless
+    /// authentication
+    Dormant,
+    /// The interface is able to transfer data
+    Up,
+}
+
+/// Policy used to compute the operational state of a network interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum LinkMode {
+    /// The operational state follows the carrier
+    Default,
+    /// The interface is `OperState::Dormant` until a userspace supplicant
+    /// brings it up, e.g. after wireless authentication
+    Dormant,
+    /// The interface is `OperState::Testing` until brought up by userspace
+    Testing,
+    /// Other mode, identified by its `IF_LINK_MODE_*` number
+    Other(u8),
+}
+
+/// Duplex mode of a link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Duplex {
+    /// Data is transferred in a single direction at a time
+    Half,
+    /// Data is transferred in both directions simultaneously
+    Full,
+}
+
+/// Origin of the hardware address of a network interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum AddrAssignType {
+    /// Permanent address of the device, e.g. burned into the network adapter
+    Permanent,
+    /// Randomly generated address
+    Random,
+    /// Address taken from another device, e.g. a bond slave
+    Stolen,
+    /// Address configured by the user
+    Set,
+    /// Other origin, identified by its `NET_ADDR_*` number
+    Other(u8),
+}
+
+impl OperState {
+    /// Returns `true` if the interface is able to transfer data.
+    ///
+    /// As done by the kernel, `OperState::Unknown` is considered up since it
+    /// is reported by the devices not tracking their state, such as the
+    /// loopback.
+    pub fn is_up(&self) -> bool {
+        matches!(self, OperState::Up | OperState::Unknown)
+    }
+}
+
+impl From<u8> for OperState {
+    /// Maps the `IF_OPER_*` numbers to an `OperState`, the unknown numbers
+    /// being mapped to `OperState::Unknown`
+    fn from(operstate: u8) -> Self {
+        match operstate {
+            1 => OperState::NotPresent,
+            2 => OperState::Down,
+            3 => OperState::LowerLayerDown,
+            4 => OperState::Testing,
+            5 => OperState::Dormant,
+            6 => OperState::Up,
+            _ => OperState::Unknown,
+        }
+    }
+}
+
+impl From<u8> for LinkMode {
+    /// Maps the `IF_LINK_MODE_*` numbers to a `LinkMode`
+    fn from(link_mode: u8) -> Self {
+        match link_mode {
+            0 => LinkMode::Default,
+            1 => LinkMode::Dormant,
+            2 => LinkMode::Testing,
+            link_mode => LinkMode::Other(link_mode),
+        }
+    }
+}
+
+impl From<u8> for AddrAssignType {
+    /// Maps the `NET_ADDR_*` numbers to an `AddrAssignType`
+    fn from(addr_assign_type: u8) -> Self {
+        match addr_assign_type {
+            0 => AddrAssignType::Permanent,
+            1 => AddrAssignType::Random,
+            2 => AddrAssignType::Stolen,
+            3 => AddrAssignType::Set,
+            addr_assign_type => AddrAssignType::Other(addr_assign_type),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Hardware (link layer) address of a network interface, such as the
+//! EUI-48 MAC address of an Ethernet adapter, the EUI-64 address of an
+//! IEEE 802.15.4 device or the 20 bytes address of an InfiniBand port.
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+/// Hardware address of a network interface
+///
+/// Addresses are displayed as lowercase colon separated hexadecimal octets,
+/// e.g. `02:fc:00:00:00:01`, other notations are available through
+/// `MacAddr::to_string_with`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MacAddr {
+    octets: [u8; MacAddr::MAX_LEN],
+    len: u8,
+}
+
+/// Notation used to represent a `MacAddr` as a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MacAddrNotation {
+    /// Colon separated octets, e.g. `02:fc:00:00:00:01`
+    Colon,
+    /// Hyphen separated octets, e.g. `02-fc-00-00-00-01`
+    Hyphen,
+    /// Dot separated groups of two octets, e.g. `02fc.0000.0001`
+    Dot,
+    /// Octets without separator, e.g. `02fc00000001`
+    Bare,
+}
+
+/// An error which can be returned when parsing a `MacAddr`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMacAddrError(());
+
+impl MacAddr {
+    /// Maximum length in bytes of a hardware address, used by InfiniBand
+    pub const MAX_LEN: usize = 20;
+
+    /// Creates a `MacAddr` from its octets.
+    ///
+    /// Returns `None` if `octets` is empty or longer than `MacAddr::MAX_LEN`,
+    /// as reported for interfaces without hardware address like tunnels.
+    pub fn new(octets: &[u8]) -> Option<Self> {
+        if octets.is_empty() || octets.len() > MacAddr::MAX_LEN {
+            return None;
+        }
+
+        let mut mac_addr = MacAddr {
+            octets: [0; MacAddr::MAX_LEN],
+            len: octets.len() as u8,
+        };
+
+        mac_addr.octets[..octets.len()].copy_from_slice(octets);
+
+        Some(mac_addr)
+    }
+
+    /// Retrieves the octets of this address
+    pub fn octets(&self) -> &[u8] {
+        &self.octets[..self.len as usize]
+    }
+
+    /// Returns `true` if every octet is `0xff`
+    pub fn is_broadcast(&self) -> bool {
+        self.octets().iter().all(|octet| *octet == 0xff)
+    }
+
+    /// Returns `true` if the individual/group bit of the first octet is set,
+    /// as defined for IEEE 802 addresses
+    pub fn is_multicast(&self) -> bool {
+        self.octets[0] & 0x01 != 0
+    }
+
+    /// Returns `true` if the individual/group bit of the first octet is not
+    /// set, as defined for IEEE 802 addresses
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Returns `true` if the universal/local bit of the first octet is set,
+    /// as defined for IEEE 802 addresses
+    pub fn is_local(&self) -> bool {
+        self.octets[0] & 0x02 != 0
+    }
+
+    /// Returns `true` if the universal/local bit of the first octet is not
+    /// set, as defined for IEEE 802 addresses
+    pub fn is_universal(&self) -> bool {
+        !self.is_local()
+    }
+
+    /// Retrieves the Organizationally Unique Identifier of an universally
+    /// administered EUI-48 or EUI-64 address.
+    ///
+    /// Returns `None` for locally administered addresses or addresses of any
+    /// other length.
+    pub fn oui(&self) -> Option<[u8; 3]> {
+        match self.len {
+            6 | 8 if self.is_universal() => Some([self.octets[0], self.octets[1], self.octets[2]]),
+            _ => None,
+        }
+    }
+
+    /// Derives the modified EUI-64 interface identifier used on IPv6
+    /// addresses from an EUI-48 or EUI-64 address.
+    ///
+    /// EUI-48 addresses are expanded by inserting `0xfffe` between the OUI and
+    /// the extension identifier, then the universal/local bit is inverted.
+    ///
+    /// Returns `None` for addresses of any other length.
+    ///
+    /// ## References
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc4291#appendix-A>
+    pub fn to_modified_eui64(&self) -> Option<[u8; 8]> {
+        let o = &self.octets;
+        let mut eui64 = match self.len {
+            6 => [o[0], o[1], o[2], 0xff, 0xfe, o[3], o[4], o[5]],
+            8 => [o[0], o[1], o[2], o[3], o[4], o[5], o[6], o[7]],
+            _ => return None,
+        };
+
+        eui64[0] ^= 0x02;
+
+        Some(eui64)
+    }
+
+    /// Formats this address using the provided notation
+    pub fn to_string_with(&self, notation: MacAddrNotation) -> String {
+        let octets = self.octets();
+        let mut string = String::with_capacity(octets.len() * 3);
+
+        for (index, octet) in octets.iter().enumerate() {
+            if index > 0 {
+                match notation {
+                    MacAddrNotation::Colon => string.push(':'),
+                    MacAddrNotation::Hyphen => string.push('-'),
+                    MacAddrNotation::Dot if index % 2 == 0 => string.push('.'),
+                    MacAddrNotation::Dot | MacAddrNotation::Bare => {}
+                }
+            }
+
+            string.push_str(&format!("{:02x}", octet));
+        }
+
+        string
+    }
+}
+
+impl Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_with(MacAddrNotation::Colon))
+    }
+}
+
+impl Debug for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MacAddr({})", self)
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = ParseMacAddrError;
+
+    /// Parses a `MacAddr` from any of the `MacAddrNotation` notations, hex
+    /// digits are case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut octets = Vec::with_capacity(MacAddr::MAX_LEN);
+
+        if s.contains(':') || s.contains('-') {
+            let separator = if s.contains(':') { ':' } else { '-' };
+
+            for group in s.split(separator) {
+                if group.is_empty() || group.len() > 2 {
+                    return Err(ParseMacAddrError(()));
+                }
+
+                octets.push(parse_hex(group)?);
+            }
+        } else {
+            let groups = if s.contains('.') {
+                s.split('.').collect::<Vec<_>>()
+            } else {
+                vec![s]
+            };
+
+            for group in groups {
+                if group.is_empty() || group.len() % 2 != 0 || (s.contains('.') && group.len() != 4)
+                {
+                    return Err(ParseMacAddrError(()));
+                }
+
+                for index in (0..group.len()).step_by(2) {
+                    octets.push(parse_hex(group.get(index..index + 2).unwrap_or_default())?);
+                }
+            }
+        }
+
+        MacAddr::new(&octets).ok_or(ParseMacAddrError(()))
+    }
+}
+
+fn parse_hex(digits: &str) -> Result<u8, ParseMacAddrError> {
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParseMacAddrError(()));
+    }
+
+    u8::from_str_radix(digits, 16).map_err(|_| ParseMacAddrError(()))
+}
+
+impl Display for ParseMacAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid hardware address syntax")
+    }
+}
+
+impl Error for ParseMacAddrError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MacAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let string = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MacAddr, MacAddrNotation};
+
+    #[test]
+    fn parses_and_formats_notations() {
+        let mac_addr = MacAddr::new(&[0x00, 0x22, 0x48, 0x03, 0xed, 0x76]).unwrap();
+
+        for (notation, string) in [
+            (MacAddrNotation::Colon, "00:22:48:03:ed:76"),
+            (MacAddrNotation::Hyphen, "00-22-48-03-ed-76"),
+            (MacAddrNotation::Dot, "0022.4803.ed76"),
+            (MacAddrNotation::Bare, "00224803ed76"),
+        ] {
+            assert_eq!(mac_addr.to_string_with(notation), string);
+            assert_eq!(string.parse::<MacAddr>().unwrap(), mac_addr);
+            assert_eq!(string.to_uppercase().parse::<MacAddr>().unwrap(), mac_addr);
+        }
+
+        assert_eq!(mac_addr.to_string(), "00:22:48:03:ed:76");
+        assert_eq!("0:22:48:3:ed:76".parse::<MacAddr>().unwrap(), mac_addr);
+
+        for invalid in [
+            "",
+            "00:22-48:03:ed:76",
+            "00:22::48",
+            "0022.48.03ed76",
+            "002",
+            "zz",
+        ] {
+            assert!(invalid.parse::<MacAddr>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn supports_variable_lengths() {
+        let infiniband = "80:00:02:08:fe:80:00:00:00:00:00:00:00:02:c9:03:00:4a:3b:2c"
+            .parse::<MacAddr>()
+            .unwrap();
+
+        assert_eq!(infiniband.octets().len(), 20);
+        assert_eq!(infiniband.oui(), None);
+        assert!(MacAddr::new(&[]).is_none());
+        assert!(MacAddr::new(&[0; 21]).is_none());
+    }
+
+    #[test]
+    fn checks_address_bits() {
+        let universal = "00:22:48:03:ed:76".parse::<MacAddr>().unwrap();
+        let local = "02:fc:00:00:00:01".parse::<MacAddr>().unwrap();
+        let multicast = "01:00:5e:00:00:01".parse::<MacAddr>().unwrap();
+
+        assert!(universal.is_unicast() && universal.is_universal());
+        assert_eq!(universal.oui(), Some([0x00, 0x22, 0x48]));
+        assert!(local.is_local());
+        assert_eq!(local.oui(), None);
+        assert!(multicast.is_multicast());
+        assert!("ff:ff:ff:ff:ff:ff"
+            .parse::<MacAddr>()
+            .unwrap()
+            .is_broadcast());
+    }
+
+    #[test]
+    fn derives_modified_eui64() {
+        let eui48 = "00:22:48:03:ed:76".parse::<MacAddr>().unwrap();
+        let eui64 = "02:22:48:ff:fe:03:ed:76".parse::<MacAddr>().unwrap();
+
+        assert_eq!(
+            eui48.to_modified_eui64(),
+            Some([0x02, 0x22, 0x48, 0xff, 0xfe, 0x03, 0xed, 0x76])
+        );
+        assert_eq!(
+            eui64.to_modified_eui64(),
+            Some([0x00, 0x22, 0x48, 0xff, 0xfe, 0x03, 0xed, 0x76])
+        );
+    }
+}
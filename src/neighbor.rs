@@ -0,0 +1,117 @@
+//! Entries of the system's neighbor tables, mapping the IP addresses of the
+//! hosts on a link to their hardware addresses through ARP for IPv4 and NDP
+//! for IPv6.
+use std::net::IpAddr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::flags::bitset;
+use crate::{MacAddr, NetworkInterface, NetworkInterfaceConfig, Result};
+
+/// An entry of a neighbor table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub struct Neighbor {
+    /// IP address of the neighbor
+    pub ip: IpAddr,
+    /// Hardware address of the neighbor, or `None` if it is not resolved yet
+    pub mac_addr: Option<MacAddr>,
+    /// Index of the network interface the neighbor is reachable through
+    pub index: u32,
+    /// Reachability state of the entry
+    pub state: NeighborState,
+    /// Flags of the entry
+    pub flags: NeighborFlags,
+}
+
+/// Reachability state of a neighbor table entry
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum NeighborState {
+    /// Resolution is in progress
+    Incomplete,
+    /// The neighbor is known to be reachable
+    Reachable,
+    /// The neighbor is not known to be reachable anymore, it will be
+    /// verified when traffic is sent to it
+    Stale,
+    /// Verification is delayed, waiting for upper layers confirmation
+    Delay,
+    /// The neighbor is being verified
+    Probe,
+    /// Resolution failed
+    Failed,
+    /// The neighbor is reachable without resolution
+    NoArp,
+    /// The entry was statically configured
+    Permanent,
+    /// Other state, identified by its `NUD_*` bits
+    Other(u16),
+}
+
+/// A set of flags for a neighbor table entry
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct NeighborFlags(u8);
+
+impl Neighbor {
+    /// Retrieves the network interface this neighbor is reachable through, or
+    /// `None` if it no longer exists
+    pub fn network_interface(&self) -> Result<Option<NetworkInterface>> {
+        NetworkInterface::by_index(self.index)
+    }
+}
+
+impl From<u16> for NeighborState {
+    /// Maps the `NUD_*` numbers to a `NeighborState`
+    fn from(state: u16) -> Self {
+        match state {
+            0x01 => NeighborState::Incomplete,
+            0x02 => NeighborState::Reachable,
+            0x04 => NeighborState::Stale,
+            0x08 => NeighborState::Delay,
+            0x10 => NeighborState::Probe,
+            0x20 => NeighborState::Failed,
+            0x40 => NeighborState::NoArp,
+            0x80 => NeighborState::Permanent,
+            state => NeighborState::Other(state),
+        }
+    }
+}
+
+bitset! {
+    NeighborFlags(u8) {
+        /// Entry is in use
+        const USE = 0x01;
+        /// Entry is a proxy entry, answering on behalf of the neighbor
+        const PROXY = 0x08;
+        /// Entry was learned externally, e.g. by a switch driver
+        const EXT_LEARNED = 0x10;
+        /// Entry is offloaded to the hardware
+        const OFFLOADED = 0x20;
+        /// Neighbor is an IPv6 router
+        const ROUTER = 0x80;
+    }
+}
+
+impl NeighborFlags {
+    /// Creates a set of flags from the raw `NTF_*` bits
+    pub const fn from_bits(bits: u8) -> Self {
+        NeighborFlags(bits)
+    }
+
+    /// Returns `true` if the entry is a proxy entry
+    pub const fn is_proxy(&self) -> bool {
+        self.contains(NeighborFlags::PROXY)
+    }
+
+    /// Returns `true` if the neighbor is an IPv6 router
+    pub const fn is_router(&self) -> bool {
+        self.contains(NeighborFlags::ROUTER)
+    }
+}
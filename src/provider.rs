@@ -0,0 +1,314 @@
+//! Source of network interfaces which can be injected into the code
+//! depending on this crate, allowing to replace the system's network
+//! interfaces with fake ones on tests.
+use std::net::IpAddr;
+
+use crate::{InterfaceQuery, MacAddr, NetworkInterface, NetworkInterfaceConfig, Result};
+
+/// A source of network interfaces.
+///
+/// The trait is object safe, so a provider can be stored as a
+/// `Box<dyn InterfaceProvider>` and replaced by a `FakeProvider` on tests.
+///
+/// ```no_run
+/// use network_interface::{InterfaceProvider, SystemProvider};
+///
+/// fn loopback_names(provider: &dyn InterfaceProvider) -> Vec<String> {
+///     provider
+///         .interfaces()
+///         .unwrap()
+///         .into_iter()
+///         .filter(|network_interface| network_interface.is_loopback())
+///         .map(|network_interface| network_interface.name)
+///         .collect()
+/// }
+///
+/// let names = loopback_names(&SystemProvider);
+/// ```
+pub trait InterfaceProvider {
+    /// Retrieves the network interfaces, sorted by index
+    fn interfaces(&self) -> Result<Vec<NetworkInterface>>;
+
+    /// Retrieves the network interfaces matching the provided query
+    ///
+    /// Unless overridden by the provider, every network interface is
+    /// retrieved with `interfaces` and filtered afterwards.
+    fn query(&self, query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+        let mut network_interfaces = self.interfaces()?;
+
+        query.retain(&mut network_interfaces);
+
+        Ok(network_interfaces)
+    }
+
+    /// Retrieves the network interface with the provided name
+    fn by_name(&self, name: &str) -> Result<Option<NetworkInterface>> {
+        Ok(self
+            .interfaces()?
+            .into_iter()
+            .find(|network_interface| network_interface.name == name))
+    }
+
+    /// Retrieves the network interface with the provided index
+    fn by_index(&self, index: u32) -> Result<Option<NetworkInterface>> {
+        Ok(self
+            .interfaces()?
+            .into_iter()
+            .find(|network_interface| network_interface.index == index))
+    }
+
+    /// Retrieves the network interface with the provided hardware address
+    fn by_mac(&self, mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+        Ok(self
+            .interfaces()?
+            .into_iter()
+            .find(|network_interface| network_interface.mac_addr.as_ref() == Some(mac_addr)))
+    }
+
+    /// Retrieves the network interface holding the provided address
+    fn by_ip(&self, ip: IpAddr) -> Result<Option<NetworkInterface>> {
+        Ok(self
+            .interfaces()?
+            .into_iter()
+            .find(|network_interface| network_interface.addr.iter().any(|addr| addr.ip() == ip)))
+    }
+}
+
+/// Provider of the system's network interfaces, as retrieved by
+/// `NetworkInterfaceConfig`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemProvider;
+
+impl InterfaceProvider for SystemProvider {
+    fn interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        NetworkInterface::show()
+    }
+
+    fn query(&self, query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+        query.run()
+    }
+
+    fn by_name(&self, name: &str) -> Result<Option<NetworkInterface>> {
+        NetworkInterface::by_name(name)
+    }
+
+    fn by_index(&self, index: u32) -> Result<Option<NetworkInterface>> {
+        NetworkInterface::by_index(index)
+    }
+
+    fn by_mac(&self, mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+        NetworkInterface::by_mac(mac_addr)
+    }
+
+    fn by_ip(&self, ip: IpAddr) -> Result<Option<NetworkInterface>> {
+        NetworkInterface::by_ip(ip)
+    }
+}
+
+impl<P: InterfaceProvider + ?Sized> InterfaceProvider for &P {
+    fn interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        (**self).interfaces()
+    }
+
+    fn query(&self, query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+        (**self).query(query)
+    }
+
+    fn by_name(&self, name: &str) -> Result<Option<NetworkInterface>> {
+        (**self).by_name(name)
+    }
+
+    fn by_index(&self, index: u32) -> Result<Option<NetworkInterface>> {
+        (**self).by_index(index)
+    }
+
+    fn by_mac(&self, mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+        (**self).by_mac(mac_addr)
+    }
+
+    fn by_ip(&self, ip: IpAddr) -> Result<Option<NetworkInterface>> {
+        (**self).by_ip(ip)
+    }
+}
+
+impl<P: InterfaceProvider + ?Sized> InterfaceProvider for Box<P> {
+    fn interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        (**self).interfaces()
+    }
+
+    fn query(&self, query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+        (**self).query(query)
+    }
+
+    fn by_name(&self, name: &str) -> Result<Option<NetworkInterface>> {
+        (**self).by_name(name)
+    }
+
+    fn by_index(&self, index: u32) -> Result<Option<NetworkInterface>> {
+        (**self).by_index(index)
+    }
+
+    fn by_mac(&self, mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+        (**self).by_mac(mac_addr)
+    }
+
+    fn by_ip(&self, ip: IpAddr) -> Result<Option<NetworkInterface>> {
+        (**self).by_ip(ip)
+    }
+}
+
+/// In-memory provider of fake network interfaces, enabled by the `test-util`
+/// feature.
+///
+/// ```
+/// use network_interface::{FakeProvider, InterfaceFlags, InterfaceProvider, NetworkInterface};
+///
+/// let provider = FakeProvider::new()
+///     .with_interface(
+///         NetworkInterface::new_afinet("lo", "127.0.0.1".parse().unwrap(), None, None, 1)
+///             .with_flags(InterfaceFlags::UP | InterfaceFlags::LOOPBACK),
+///     )
+///     .with_interface(NetworkInterface::new_afinet(
+///         "eth0",
+///         "192.0.2.1".parse().unwrap(),
+///         Some("255.255.255.0".parse().unwrap()),
+///         None,
+///         2,
+///     ));
+///
+/// assert_eq!(provider.interfaces().unwrap().len(), 2);
+/// assert_eq!(provider.by_name("eth0").unwrap().unwrap().index, 2);
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FakeProvider {
+    network_interfaces: Vec<NetworkInterface>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl FakeProvider {
+    /// Creates a provider without network interfaces
+    pub fn new() -> Self {
+        FakeProvider::default()
+    }
+
+    /// Adds a network interface to the provider.
+    ///
+    /// Interfaces are merged by index as done by the system backends, the
+    /// addresses of an interface with an index already provided are appended
+    /// to the existing interface.
+    pub fn with_interface(mut self, network_interface: NetworkInterface) -> Self {
+        let position = self
+            .network_interfaces
+            .iter()
+            .position(|existing| existing.index == network_interface.index);
+
+        match position {
+            Some(position) => self.network_interfaces[position]
+                .addr
+                .extend(network_interface.addr),
+            None => {
+                self.network_interfaces.push(network_interface);
+                self.network_interfaces
+                    .sort_by_key(|network_interface| network_interface.index);
+            }
+        }
+
+        self
+    }
+
+    /// Adds every provided network interface, see `FakeProvider::with_interface`
+    pub fn with_interfaces<I>(self, network_interfaces: I) -> Self
+    where
+        I: IntoIterator<Item = NetworkInterface>,
+    {
+        network_interfaces
+            .into_iter()
+            .fold(self, |provider, network_interface| {
+                provider.with_interface(network_interface)
+            })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl InterfaceProvider for FakeProvider {
+    fn interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        Ok(self.network_interfaces.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FakeProvider, InterfaceProvider};
+    use crate::{Family, InterfaceFlags, InterfaceQuery, NetworkInterface};
+
+    fn make_provider() -> Box<dyn InterfaceProvider> {
+        let up = InterfaceFlags::UP | InterfaceFlags::RUNNING;
+
+        Box::new(
+            FakeProvider::new()
+                .with_interface(
+                    NetworkInterface::new_afinet(
+                        "eth0",
+                        "192.0.2.1".parse().unwrap(),
+                        None,
+                        None,
+                        2,
+                    )
+                    .with_mac_addr(Some("02:fc:00:00:00:01".parse().unwrap()))
+                    .with_flags(up),
+                )
+                .with_interfaces(vec![
+                    NetworkInterface::new_afinet("lo", "127.0.0.1".parse().unwrap(), None, None, 1)
+                        .with_flags(up | InterfaceFlags::LOOPBACK),
+                    NetworkInterface::new_afinet6(
+                        "eth0",
+                        "fd00::1".parse().unwrap(),
+                        None,
+                        None,
+                        2,
+                    ),
+                ]),
+        )
+    }
+
+    #[test]
+    fn provides_fake_interfaces() {
+        let provider = make_provider();
+        let network_interfaces = provider.interfaces().unwrap();
+
+        assert_eq!(network_interfaces.len(), 2);
+        assert_eq!(network_interfaces[0].name, "lo");
+        assert_eq!(network_interfaces[1].addr.len(), 2);
+        assert_eq!(provider.by_index(2).unwrap().unwrap().name, "eth0");
+        assert_eq!(
+            provider
+                .by_mac(&"02:fc:00:00:00:01".parse().unwrap())
+                .unwrap()
+                .unwrap()
+                .index,
+            2
+        );
+        assert_eq!(
+            provider
+                .by_ip("fd00::1".parse().unwrap())
+                .unwrap()
+                .unwrap()
+                .name,
+            "eth0"
+        );
+        assert_eq!(provider.by_name("eth1").unwrap(), None);
+    }
+
+    #[test]
+    fn queries_fake_interfaces() {
+        let provider = make_provider();
+        let network_interfaces = provider
+            .query(&InterfaceQuery::new().family(Family::V6).exclude_loopback())
+            .unwrap();
+
+        assert_eq!(network_interfaces.len(), 1);
+        assert_eq!(network_interfaces[0].addr.len(), 1);
+        assert!(network_interfaces[0].addr[0].ip().is_ipv6());
+    }
+}
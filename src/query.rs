@@ -0,0 +1,251 @@
+//! Filtered enumeration of the system's network interfaces, allowing the
+//! backends to skip the records not matching the filters instead of
+//! retrieving every network interface.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Addr, InterfaceFlags, NetworkInterface, Result};
+
+/// Address family of a network interface address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Family {
+    /// IPv4 addresses
+    V4,
+    /// IPv6 addresses
+    V6,
+}
+
+/// Builder of a filtered network interfaces enumeration.
+///
+/// An interface administratively up could still be unable to transfer data,
+/// e.g. with its cable unplugged, `oper_up_only` skips such interfaces unlike
+/// `up_only`.
+///
+/// ```no_run
+/// use network_interface::{Family, InterfaceQuery};
+///
+/// let network_interfaces = InterfaceQuery::new()
+///     .family(Family::V4)
+///     .up_only()
+///     .exclude_loopback()
+///     .name_glob("eth*")
+///     .run()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceQuery {
+    family: Option<Family>,
+    up_only: bool,
+    oper_up_only: bool,
+    exclude_loopback: bool,
+    name_glob: Option<String>,
+}
+
+impl InterfaceQuery {
+    /// Creates a query matching every network interface
+    pub fn new() -> Self {
+        InterfaceQuery::default()
+    }
+
+    /// Only retrieves the addresses of the provided family, skipping the
+    /// network interfaces without addresses of such family
+    pub fn family(mut self, family: Family) -> Self {
+        self.family = Some(family);
+        self
+    }
+
+    /// Skips the network interfaces which are not administratively up, see
+    /// `NetworkInterface::is_up`
+    pub fn up_only(mut self) -> Self {
+        self.up_only = true;
+        self
+    }
+
+    /// Skips the network interfaces which are not operationally up, see
+    /// `NetworkInterface::is_oper_up`
+    pub fn oper_up_only(mut self) -> Self {
+        self.oper_up_only = true;
+        self
+    }
+
+    /// Skips the loopback network interfaces
+    pub fn exclude_loopback(mut self) -> Self {
+        self.exclude_loopback = true;
+        self
+    }
+
+    /// Skips the network interfaces whose name does not match the provided
+    /// glob pattern, where `*` matches any sequence of characters and `?`
+    /// matches a single character
+    pub fn name_glob<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.name_glob = Some(pattern.into());
+        self
+    }
+
+    /// Retrieves the network interfaces matching this query, sorted by index
+    pub fn run(&self) -> Result<Vec<NetworkInterface>> {
+        crate::target::query(self)
+    }
+
+    /// Retrieves the family filter of this query
+    pub(crate) fn address_family(&self) -> Option<Family> {
+        self.family
+    }
+
+    /// Returns `true` if a network interface with the provided name and flags
+    /// matches this query, regardless of its addresses.
+    ///
+    /// The operational state is checked with the `RUNNING` flag, which is set
+    /// by the kernel for the states considered up, the state itself is checked
+    /// by `retain`.
+    pub(crate) fn matches_link(&self, name: &str, flags: InterfaceFlags) -> bool {
+        if self.up_only && !flags.is_up() {
+            return false;
+        }
+
+        if self.oper_up_only && !(flags.is_up() && flags.is_running()) {
+            return false;
+        }
+
+        if self.exclude_loopback && flags.is_loopback() {
+            return false;
+        }
+
+        match &self.name_glob {
+            Some(pattern) => glob_match(pattern, name),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the provided address matches this query
+    pub(crate) fn matches_addr(&self, addr: &Addr) -> bool {
+        !matches!(
+            (self.family, addr),
+            (Some(Family::V4), Addr::V6(_)) | (Some(Family::V6), Addr::V4(_))
+        )
+    }
+
+    /// Retains the network interfaces and addresses matching this query
+    pub(crate) fn retain(&self, network_interfaces: &mut Vec<NetworkInterface>) {
+        for network_interface in network_interfaces.iter_mut() {
+            network_interface
+                .addr
+                .retain(|addr| self.matches_addr(addr));
+        }
+
+        network_interfaces.retain(|network_interface| {
+            self.matches_link(&network_interface.name, network_interface.flags)
+                && (!self.oper_up_only || network_interface.is_oper_up())
+                && (self.family.is_none() || !network_interface.addr.is_empty())
+        });
+    }
+}
+
+/// Matches `name` against a glob pattern supporting the `*` and `?`
+/// wildcards
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` on the pattern alongside the position on the
+    // name it is matching up to, used to backtrack on mismatches
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, Family, InterfaceQuery};
+    use crate::{Addr, InterfaceFlags, NetworkInterface, OperState};
+
+    #[test]
+    fn matches_glob_patterns() {
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("eth*", "eth"));
+        assert!(glob_match("*0", "eth0"));
+        assert!(glob_match("e?h*1", "eth0.1"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*a*b", "xaxxab"));
+        assert!(!glob_match("eth*", "wlan0"));
+        assert!(!glob_match("eth?", "eth10"));
+        assert!(!glob_match("", "eth0"));
+    }
+
+    #[test]
+    fn retains_matching_interfaces() {
+        let up = InterfaceFlags::UP | InterfaceFlags::RUNNING;
+        let mut network_interfaces = vec![
+            NetworkInterface::new_afinet("lo", "127.0.0.1".parse().unwrap(), None, None, 1)
+                .with_flags(up | InterfaceFlags::LOOPBACK),
+            NetworkInterface::new_afinet6("eth0", "fd00::1".parse().unwrap(), None, None, 2)
+                .with_flags(up),
+            NetworkInterface::new_afinet("eth1", "192.0.2.1".parse().unwrap(), None, None, 3)
+                .with_flags(up),
+            NetworkInterface::new_afinet("eth2", "192.0.2.2".parse().unwrap(), None, None, 4),
+            NetworkInterface::new_afinet("wlan0", "192.0.2.3".parse().unwrap(), None, None, 5)
+                .with_flags(up),
+        ];
+
+        InterfaceQuery::new()
+            .family(Family::V4)
+            .up_only()
+            .exclude_loopback()
+            .name_glob("eth*")
+            .retain(&mut network_interfaces);
+
+        assert_eq!(network_interfaces.len(), 1);
+        assert_eq!(network_interfaces[0].name, "eth1");
+        assert!(matches!(network_interfaces[0].addr[0], Addr::V4(_)));
+    }
+
+    #[test]
+    fn retains_operational_interfaces() {
+        let mut unplugged =
+            NetworkInterface::new_afinet("eth0", "192.0.2.1".parse().unwrap(), None, None, 2)
+                .with_flags(InterfaceFlags::UP | InterfaceFlags::RUNNING);
+
+        // the flags may be outdated compared to the reported state
+        unplugged.link.operstate = Some(OperState::LowerLayerDown);
+
+        let mut network_interfaces = vec![
+            unplugged,
+            NetworkInterface::new_afinet("eth1", "192.0.2.2".parse().unwrap(), None, None, 3)
+                .with_flags(InterfaceFlags::UP),
+            NetworkInterface::new_afinet("eth2", "192.0.2.3".parse().unwrap(), None, None, 4)
+                .with_flags(InterfaceFlags::UP | InterfaceFlags::RUNNING),
+        ];
+        let mut up = network_interfaces.clone();
+
+        InterfaceQuery::new().up_only().retain(&mut up);
+        InterfaceQuery::new()
+            .oper_up_only()
+            .retain(&mut network_interfaces);
+
+        assert_eq!(up.len(), 3);
+        assert_eq!(network_interfaces.len(), 1);
+        assert_eq!(network_interfaces[0].name, "eth2");
+    }
+}
@@ -0,0 +1,216 @@
+//! Routes of the system's routing tables, linked to the network interfaces
+//! they go through by index.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::{AddrScope, Family, NetworkInterface, NetworkInterfaceConfig, Result};
+
+/// Identifier of the main routing table, used by default on Linux
+pub const MAIN_TABLE: u32 = 254;
+
+/// Identifier of the local routing table, holding the routes to the local
+/// and broadcast addresses on Linux
+pub const LOCAL_TABLE: u32 = 255;
+
+/// A route of a routing table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub struct Route {
+    /// Network address of the destination prefix
+    pub destination: IpAddr,
+    /// Prefix length of the destination, `0` for default routes
+    pub prefix_len: u8,
+    /// Next hop, or `None` if the destination is directly reachable
+    pub gateway: Option<IpAddr>,
+    /// Index of the output network interface, or `0` if there is none such
+    /// as for unreachable routes
+    pub index: u32,
+    /// Priority of the route, lower is preferred
+    pub metric: u32,
+    /// Routing table holding the route, e.g. `MAIN_TABLE`
+    pub table: u32,
+    /// Originator of the route
+    pub protocol: RouteProtocol,
+    /// Distance to the destination
+    pub scope: AddrScope,
+    /// Preferred source address for packets sent through the route
+    pub source: Option<IpAddr>,
+    /// Type of the route
+    pub kind: RouteKind,
+}
+
+/// A default route of the main routing table alongside the network interface
+/// it goes through
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
+pub struct DefaultGateway {
+    /// Address family of the default route
+    pub family: Family,
+    /// Output network interface of the default route
+    pub network_interface: NetworkInterface,
+    /// Next hop, or `None` if the default route points to the interface such
+    /// as on point-to-point links
+    pub gateway: Option<IpAddr>,
+    /// Priority of the default route, lower is preferred
+    pub metric: u32,
+}
+
+/// Originator of a route
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum RouteProtocol {
+    /// Unknown originator
+    Unspec,
+    /// Installed by an ICMP redirect
+    Redirect,
+    /// Installed by the kernel, e.g. for the prefix of an address
+    Kernel,
+    /// Installed during boot
+    Boot,
+    /// Installed by the administrator
+    Static,
+    /// Installed from a router advertisement
+    Ra,
+    /// Installed by a DHCP client
+    Dhcp,
+    /// Installed by another routing daemon, identified by its number
+    Other(u8),
+}
+
+/// Type of a route
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum RouteKind {
+    /// Gateway or direct route
+    Unicast,
+    /// Local interface route
+    Local,
+    /// Local broadcast route, sent as broadcast
+    Broadcast,
+    /// Local broadcast route, sent as unicast
+    Anycast,
+    /// Multicast route
+    Multicast,
+    /// Packet dropping route
+    Blackhole,
+    /// Unreachable destination
+    Unreachable,
+    /// Packet rejection route
+    Prohibit,
+    /// Continues the lookup in another table
+    Throw,
+    /// Other type, identified by its number
+    Other(u8),
+}
+
+impl Route {
+    /// Returns `true` if this is a default route, matching every destination
+    pub fn is_default(&self) -> bool {
+        self.prefix_len == 0
+    }
+
+    /// Retrieves the output network interface of this route, or `None` if
+    /// there is none or it no longer exists
+    pub fn network_interface(&self) -> Result<Option<NetworkInterface>> {
+        if self.index == 0 {
+            return Ok(None);
+        }
+
+        NetworkInterface::by_index(self.index)
+    }
+}
+
+/// Retrieves the IPv4 and IPv6 default routes of the main routing table,
+/// ordered by metric.
+///
+/// Default routes going through a network interface which no longer exists
+/// are skipped.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn default_gateways() -> Result<Vec<DefaultGateway>> {
+    let routes = crate::routes()?;
+    let network_interfaces = NetworkInterface::show()?;
+    let mut default_gateways = Vec::new();
+
+    for route in routes {
+        if !route.is_default() || route.table != MAIN_TABLE || route.kind != RouteKind::Unicast {
+            continue;
+        }
+
+        let network_interface = network_interfaces
+            .iter()
+            .find(|network_interface| network_interface.index == route.index);
+
+        if let Some(network_interface) = network_interface {
+            default_gateways.push(DefaultGateway {
+                family: match route.destination {
+                    IpAddr::V4(_) => Family::V4,
+                    IpAddr::V6(_) => Family::V6,
+                },
+                network_interface: network_interface.clone(),
+                gateway: route.gateway,
+                metric: route.metric,
+            });
+        }
+    }
+
+    // stable to keep the kernel order between routes of the same metric
+    default_gateways.sort_by_key(|default_gateway| default_gateway.metric);
+
+    Ok(default_gateways)
+}
+
+/// Retrieves the network interface of the preferred default route of the
+/// provided family, or `None` if there is no default route
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn default_route_interface(family: Family) -> Result<Option<NetworkInterface>> {
+    let default_gateway = default_gateways()?
+        .into_iter()
+        .find(|default_gateway| default_gateway.family == family);
+
+    Ok(default_gateway.map(|default_gateway| default_gateway.network_interface))
+}
+
+impl From<u8> for RouteProtocol {
+    /// Maps the `RTPROT_*` numbers to a `RouteProtocol`
+    fn from(protocol: u8) -> Self {
+        match protocol {
+            0 => RouteProtocol::Unspec,
+            1 => RouteProtocol::Redirect,
+            2 => RouteProtocol::Kernel,
+            3 => RouteProtocol::Boot,
+            4 => RouteProtocol::Static,
+            9 => RouteProtocol::Ra,
+            16 => RouteProtocol::Dhcp,
+            protocol => RouteProtocol::Other(protocol),
+        }
+    }
+}
+
+impl From<u8> for RouteKind {
+    /// Maps the `RTN_*` numbers to a `RouteKind`
+    fn from(kind: u8) -> Self {
+        match kind {
+            1 => RouteKind::Unicast,
+            2 => RouteKind::Local,
+            3 => RouteKind::Broadcast,
+            4 => RouteKind::Anycast,
+            5 => RouteKind::Multicast,
+            6 => RouteKind::Blackhole,
+            7 => RouteKind::Unreachable,
+            8 => RouteKind::Prohibit,
+            9 => RouteKind::Throw,
+            kind => RouteKind::Other(kind),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Traffic counters of a network interface, as accumulated by the system
+//! since the interface was created.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Traffic statistics of a network interface
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct InterfaceStats {
+    /// Amount of bytes received
+    pub rx_bytes: u64,
+    /// Amount of bytes transmitted
+    pub tx_bytes: u64,
+    /// Amount of packets received
+    pub rx_packets: u64,
+    /// Amount of packets transmitted
+    pub tx_packets: u64,
+    /// Amount of bad packets received
+    pub rx_errors: u64,
+    /// Amount of packets which failed to be transmitted
+    pub tx_errors: u64,
+    /// Amount of packets received but dropped, e.g. due to lack of buffers
+    pub rx_dropped: u64,
+    /// Amount of packets dropped on transmission
+    pub tx_dropped: u64,
+    /// Amount of multicast packets received
+    pub multicast: u64,
+    /// Amount of collisions detected on transmission
+    pub collisions: u64,
+}
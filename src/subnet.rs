@@ -0,0 +1,463 @@
+//! Subnet arithmetic for network interface addresses based on their netmask,
+//! alongside the CIDR notation (`192.168.0.16/24`) for `V4IfAddr` and
+//! `V6IfAddr`.
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::{Addr, Error, Result, V4IfAddr, V6IfAddr};
+
+/// An error which can be returned when parsing a `V4IfAddr` or a `V6IfAddr`
+/// from the CIDR notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIfAddrError(());
+
+/// Iterator over the host addresses of a subnet, created with `Addr::hosts`
+#[derive(Debug, Clone)]
+pub struct Hosts {
+    next: u128,
+    last: u128,
+    is_ipv6: bool,
+    done: bool,
+}
+
+/// Creates the IPv4 netmask for the provided prefix length
+pub(crate) fn ipv4_netmask(prefix_len: u8) -> Ipv4Addr {
+    let bits = u32::MAX
+        .checked_shl(32 - u32::from(prefix_len.min(32)))
+        .unwrap_or(0);
+
+    Ipv4Addr::from(bits)
+}
+
+/// Creates the IPv6 netmask for the provided prefix length
+pub(crate) fn ipv6_netmask(prefix_len: u8) -> Ipv6Addr {
+    let bits = u128::MAX
+        .checked_shl(128 - u32::from(prefix_len.min(128)))
+        .unwrap_or(0);
+
+    Ipv6Addr::from(bits)
+}
+
+/// Retrieves the amount of leading ones on a netmask of `width` bits, or
+/// `None` if the ones are not contiguous
+fn mask_prefix_len(mask: u128, width: u32) -> Option<u8> {
+    let mask = mask << (128 - width);
+    let prefix_len = mask.leading_ones();
+
+    if mask.checked_shl(prefix_len).unwrap_or(0) != 0 {
+        return None;
+    }
+
+    Some(prefix_len as u8)
+}
+
+impl V4IfAddr {
+    /// Retrieves the prefix length of the netmask, or `None` if there is no
+    /// netmask.
+    ///
+    /// Fails with `Error::NonContiguousNetmask` if the netmask bits are not
+    /// contiguous, as such netmask has no prefix length.
+    pub fn prefix_len(&self) -> Result<Option<u8>> {
+        match self.netmask {
+            Some(netmask) => mask_prefix_len(u32::from(netmask).into(), 32)
+                .map(Some)
+                .ok_or_else(|| Error::NonContiguousNetmask(netmask.into())),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the network address, or `None` if there is no netmask
+    pub fn network(&self) -> Option<Ipv4Addr> {
+        self.netmask
+            .map(|netmask| Ipv4Addr::from(u32::from(self.ip) & u32::from(netmask)))
+    }
+
+    /// Returns `true` if `ip` belongs to the subnet of this address.
+    ///
+    /// Without netmask, only the address itself belongs to the subnet.
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        let netmask = u32::from(self.netmask.unwrap_or(Ipv4Addr::BROADCAST));
+
+        u32::from(self.ip) & netmask == u32::from(ip) & netmask
+    }
+
+    /// Retrieves the broadcast address provided by the system, or computes it
+    /// from the netmask when the system provides none.
+    ///
+    /// No broadcast address is computed for point-to-point links or for
+    /// subnets with a prefix length of 31 or 32 bits, which have no room for
+    /// a broadcast address.
+    ///
+    /// ## References
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc3021>
+    pub fn effective_broadcast(&self) -> Option<Ipv4Addr> {
+        if self.broadcast.is_some() || self.destination.is_some() {
+            return self.broadcast;
+        }
+
+        let netmask = u32::from(self.netmask?);
+
+        if netmask.count_zeros() < 2 {
+            return None;
+        }
+
+        Some(Ipv4Addr::from(u32::from(self.ip) | !netmask))
+    }
+}
+
+impl V6IfAddr {
+    /// Retrieves the prefix length of the netmask, or `None` if there is no
+    /// netmask.
+    ///
+    /// Fails with `Error::NonContiguousNetmask` if the netmask bits are not
+    /// contiguous, as such netmask has no prefix length.
+    pub fn prefix_len(&self) -> Result<Option<u8>> {
+        match self.netmask {
+            Some(netmask) => mask_prefix_len(u128::from(netmask), 128)
+                .map(Some)
+                .ok_or_else(|| Error::NonContiguousNetmask(netmask.into())),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the network address, or `None` if there is no netmask
+    pub fn network(&self) -> Option<Ipv6Addr> {
+        self.netmask
+            .map(|netmask| Ipv6Addr::from(u128::from(self.ip) & u128::from(netmask)))
+    }
+
+    /// Returns `true` if `ip` belongs to the subnet of this address.
+    ///
+    /// Without netmask, only the address itself belongs to the subnet.
+    pub fn contains(&self, ip: Ipv6Addr) -> bool {
+        let netmask = self.netmask.map(u128::from).unwrap_or(u128::MAX);
+
+        u128::from(self.ip) & netmask == u128::from(ip) & netmask
+    }
+}
+
+impl Addr {
+    /// Retrieves the prefix length of the netmask, or `None` if there is no
+    /// netmask.
+    ///
+    /// Fails with `Error::NonContiguousNetmask` if the netmask bits are not
+    /// contiguous, as such netmask has no prefix length.
+    pub fn prefix_len(self) -> Result<Option<u8>> {
+        match self {
+            Addr::V4(ifaddr_v4) => ifaddr_v4.prefix_len(),
+            Addr::V6(ifaddr_v6) => ifaddr_v6.prefix_len(),
+        }
+    }
+
+    /// Retrieves the network address, or `None` if there is no netmask
+    pub fn network(self) -> Option<IpAddr> {
+        match self {
+            Addr::V4(ifaddr_v4) => ifaddr_v4.network().map(Into::into),
+            Addr::V6(ifaddr_v6) => ifaddr_v6.network().map(Into::into),
+        }
+    }
+
+    /// Returns `true` if `ip` belongs to the subnet of this address.
+    ///
+    /// Addresses of a different family never belong to the subnet, and
+    /// without netmask only the address itself belongs to it.
+    pub fn contains(self, ip: IpAddr) -> bool {
+        match (self, ip) {
+            (Addr::V4(ifaddr_v4), IpAddr::V4(ip)) => ifaddr_v4.contains(ip),
+            (Addr::V6(ifaddr_v6), IpAddr::V6(ip)) => ifaddr_v6.contains(ip),
+            _ => false,
+        }
+    }
+
+    /// Iterates over the host addresses of the subnet of this address.
+    ///
+    /// For IPv4 subnets the network and broadcast addresses are excluded,
+    /// except for subnets with a prefix length of 31 bits where both
+    /// addresses are usable hosts. Every address of IPv6 subnets is included.
+    /// Without netmask only the address itself is yielded.
+    ///
+    /// Fails with `Error::NonContiguousNetmask` if the netmask bits are not
+    /// contiguous, as the hosts of such subnet are not a range.
+    pub fn hosts(self) -> Result<Hosts> {
+        let (ip, prefix_len, width) = match self {
+            Addr::V4(ifaddr_v4) => (
+                u128::from(u32::from(ifaddr_v4.ip)),
+                ifaddr_v4.prefix_len()?.unwrap_or(32),
+                32,
+            ),
+            Addr::V6(ifaddr_v6) => (
+                u128::from(ifaddr_v6.ip),
+                ifaddr_v6.prefix_len()?.unwrap_or(128),
+                128,
+            ),
+        };
+
+        let host_bits = u32::from(width - prefix_len);
+        let host_mask = 1u128.checked_shl(host_bits).unwrap_or(0).wrapping_sub(1);
+        let mut next = ip & !host_mask;
+        let mut last = next | host_mask;
+
+        if width == 32 && host_bits >= 2 {
+            next += 1;
+            last -= 1;
+        }
+
+        Ok(Hosts {
+            next,
+            last,
+            is_ipv6: width == 128,
+            done: false,
+        })
+    }
+}
+
+impl Iterator for Hosts {
+    type Item = IpAddr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let host = self.next;
+
+        if host == self.last {
+            self.done = true;
+        } else {
+            self.next += 1;
+        }
+
+        if self.is_ipv6 {
+            Some(Ipv6Addr::from(host).into())
+        } else {
+            Some(Ipv4Addr::from(host as u32).into())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let len = usize::try_from(self.last - self.next)
+            .ok()
+            .and_then(|len| len.checked_add(1));
+
+        (len.unwrap_or(usize::MAX), len)
+    }
+}
+
+impl Display for V4IfAddr {
+    /// Formats the address in CIDR notation, e.g. `192.168.0.16/24`.
+    ///
+    /// Non-contiguous netmasks are written in dotted notation instead, e.g.
+    /// `10.0.0.1/255.0.255.0`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ip)?;
+
+        match (self.prefix_len(), self.netmask) {
+            (Ok(Some(prefix_len)), _) => write!(f, "/{}", prefix_len),
+            (Err(_), Some(netmask)) => write!(f, "/{}", netmask),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for V6IfAddr {
+    /// Formats the address in CIDR notation, alongside the scope id when
+    /// available, e.g. `fe80::1%2/64`.
+    ///
+    /// Non-contiguous netmasks are written in full instead.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ip)?;
+
+        if self.scope_id != 0 {
+            write!(f, "%{}", self.scope_id)?;
+        }
+
+        match (self.prefix_len(), self.netmask) {
+            (Ok(Some(prefix_len)), _) => write!(f, "/{}", prefix_len),
+            (Err(_), Some(netmask)) => write!(f, "/{}", netmask),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for V4IfAddr {
+    type Err = ParseIfAddrError;
+
+    /// Parses an address in CIDR notation, the netmask may also be written
+    /// in dotted notation, e.g. `192.168.0.16/255.255.255.0`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (ip, netmask) = split_netmask(s);
+        let ip = ip.parse::<Ipv4Addr>().map_err(|_| ParseIfAddrError(()))?;
+        let netmask = match netmask {
+            Some(netmask) if netmask.contains('.') => Some(
+                netmask
+                    .parse::<Ipv4Addr>()
+                    .map_err(|_| ParseIfAddrError(()))?,
+            ),
+            Some(prefix_len) => Some(ipv4_netmask(parse_prefix_len(prefix_len, 32)?)),
+            None => None,
+        };
+
+        Ok(V4IfAddr {
+            ip,
+            broadcast: None,
+            destination: None,
+            netmask,
+            label: None,
+        })
+    }
+}
+
+impl FromStr for V6IfAddr {
+    type Err = ParseIfAddrError;
+
+    /// Parses an address in CIDR notation with an optional numeric scope id,
+    /// e.g. `fe80::1%2/64`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (ip, netmask) = split_netmask(s);
+        let (ip, scope_id) = match ip.find('%') {
+            Some(index) => (
+                &ip[..index],
+                ip[index + 1..]
+                    .parse::<u32>()
+                    .map_err(|_| ParseIfAddrError(()))?,
+            ),
+            None => (ip, 0),
+        };
+        let ip = ip.parse::<Ipv6Addr>().map_err(|_| ParseIfAddrError(()))?;
+        let netmask = match netmask {
+            Some(prefix_len) => Some(ipv6_netmask(parse_prefix_len(prefix_len, 128)?)),
+            None => None,
+        };
+
+        Ok(V6IfAddr {
+            ip,
+            broadcast: None,
+            destination: None,
+            netmask,
+            scope_id,
+            flags: None,
+            lifetimes: None,
+        })
+    }
+}
+
+fn split_netmask(s: &str) -> (&str, Option<&str>) {
+    match s.find('/') {
+        Some(index) => (&s[..index], Some(&s[index + 1..])),
+        None => (s, None),
+    }
+}
+
+fn parse_prefix_len(prefix_len: &str, width: u8) -> std::result::Result<u8, ParseIfAddrError> {
+    if prefix_len.is_empty() || !prefix_len.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ParseIfAddrError(()));
+    }
+
+    match prefix_len.parse::<u8>() {
+        Ok(prefix_len) if prefix_len <= width => Ok(prefix_len),
+        _ => Err(ParseIfAddrError(())),
+    }
+}
+
+impl Display for ParseIfAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid interface address syntax")
+    }
+}
+
+impl StdError for ParseIfAddrError {}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::{Addr, Error, V4IfAddr, V6IfAddr};
+
+    #[test]
+    fn computes_ipv4_subnet() {
+        let ifaddr_v4 = "192.168.0.16/24".parse::<V4IfAddr>().unwrap();
+        let addr = Addr::V4(ifaddr_v4);
+
+        assert_eq!(addr.prefix_len().unwrap(), Some(24));
+        assert_eq!(addr.network(), Some(IpAddr::from([192, 168, 0, 0])));
+        assert!(addr.contains(IpAddr::from([192, 168, 0, 200])));
+        assert!(!addr.contains(IpAddr::from([192, 168, 1, 1])));
+        assert!(!addr.contains("::1".parse().unwrap()));
+        assert_eq!(
+            ifaddr_v4.effective_broadcast(),
+            Some(Ipv4Addr::new(192, 168, 0, 255))
+        );
+
+        let hosts = addr.hosts().unwrap().collect::<Vec<_>>();
+
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], IpAddr::from([192, 168, 0, 1]));
+        assert_eq!(hosts[253], IpAddr::from([192, 168, 0, 254]));
+        assert_eq!(ifaddr_v4.to_string(), "192.168.0.16/24");
+    }
+
+    #[test]
+    fn computes_small_ipv4_subnets() {
+        let point_to_point = Addr::V4("10.0.0.0/31".parse().unwrap());
+        let host = "10.0.0.1/32".parse::<V4IfAddr>().unwrap();
+
+        assert_eq!(point_to_point.hosts().unwrap().count(), 2);
+        assert_eq!(Addr::V4(host).hosts().unwrap().count(), 1);
+        assert_eq!(host.effective_broadcast(), None);
+    }
+
+    #[test]
+    fn computes_ipv6_subnet() {
+        let ifaddr_v6 = "fe80::1%2/64".parse::<V6IfAddr>().unwrap();
+        let addr = Addr::V6(ifaddr_v6);
+
+        assert_eq!(ifaddr_v6.scope_id, 2);
+        assert_eq!(addr.prefix_len().unwrap(), Some(64));
+        assert_eq!(addr.network(), Some("fe80::".parse().unwrap()));
+        assert!(addr.contains("fe80::ffff:1".parse().unwrap()));
+        assert!(!addr.contains("fe80:0:0:1::1".parse().unwrap()));
+        assert_eq!(addr.hosts().unwrap().size_hint().1, None);
+        assert_eq!(ifaddr_v6.to_string(), "fe80::1%2/64");
+
+        let hosts = Addr::V6("2001:db8::/126".parse().unwrap())
+            .hosts()
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(hosts.len(), 4);
+        assert_eq!(hosts[3], "2001:db8::3".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn reports_non_contiguous_netmasks() {
+        let ifaddr_v4 = "10.0.0.1/255.0.255.0".parse::<V4IfAddr>().unwrap();
+        let addr = Addr::V4(ifaddr_v4);
+
+        assert!(matches!(
+            addr.prefix_len(),
+            Err(Error::NonContiguousNetmask(_))
+        ));
+        assert!(addr.hosts().is_err());
+        assert!(addr.contains(IpAddr::from([10, 1, 0, 2])));
+        assert_eq!(ifaddr_v4.to_string(), "10.0.0.1/255.0.255.0");
+    }
+
+    #[test]
+    fn rejects_invalid_cidr() {
+        for invalid in ["", "10.0.0.1/33", "10.0.0.1/", "10.0.0.1/+8", "fe80::1"] {
+            assert!(invalid.parse::<V4IfAddr>().is_err(), "{}", invalid);
+        }
+
+        for invalid in ["fe80::1/129", "fe80::1%eth0", "10.0.0.1"] {
+            assert!(invalid.parse::<V6IfAddr>().is_err(), "{}", invalid);
+        }
+    }
+}
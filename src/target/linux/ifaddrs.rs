@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::unix::ffi::OsStrExt;
+
+use libc::{sockaddr_in, sockaddr_in6, AF_INET, AF_INET6, if_nametoindex, sockaddr_ll, AF_PACKET};
+
+use crate::target::getifaddrs;
+use crate::{
+    Addr, AddrLabel, Family, InterfaceKind, InterfaceQuery, LinkDetails, MacAddr, NetworkInterface,
+    Result, V4IfAddr, V6IfAddr,
+};
+use crate::utils::{
+    ipv4_from_in_addr, ipv6_from_in6_addr, make_interface_flags, make_ipv4_netmask,
+    make_ipv6_netmask, split_broadcast_destination,
+};
+
+use super::rtnetlink::adjust_address;
+use super::sysfs::Sysfs;
+
+/// Retrieves network interfaces by walking the linked list provided by
+/// `getifaddrs`
+pub(crate) fn show() -> Result<Vec<NetworkInterface>> {
+    query(&InterfaceQuery::new())
+}
+
+/// Retrieves the network interfaces matching the provided query by walking
+/// the linked list provided by `getifaddrs`, skipping the records of other
+/// interfaces or address families
+pub(crate) fn query(query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+    let mut network_interfaces: BTreeMap<u32, NetworkInterface> = BTreeMap::new();
+
+    for netifa in getifaddrs()? {
+        let netifa_addr = netifa.ifa_addr;
+        let netifa_family = if netifa_addr.is_null() {
+            continue;
+        } else {
+            unsafe { (*netifa_addr).sa_family as i32 }
+        };
+
+        match (netifa_family, query.address_family()) {
+            (AF_INET, Some(Family::V6)) | (AF_INET6, Some(Family::V4)) => continue,
+            _ => {}
+        }
+
+        let os_name = make_netifa_name(&netifa);
+        let name = os_name.to_string_lossy().into_owned();
+        // labeled IPv4 addresses are named after their label, e.g. `eth0:1`
+        let link_name = name.split(':').next().unwrap_or_default();
+
+        if !query.matches_link(link_name, make_interface_flags(netifa.ifa_flags)) {
+            continue;
+        }
+
+        let mut network_interface = match netifa_family {
+            AF_PACKET => {
+                let mac_addr = make_mac_addr(&netifa);
+                let index = netifa_index(&netifa);
+                NetworkInterface {
+                    name,
+                    os_name,
+                    addr: Vec::new(),
+                    mac_addr,
+                    index,
+                    flags: make_interface_flags(netifa.ifa_flags),
+                    kind: InterfaceKind::Unknown,
+                    link: LinkDetails::default(),
+                }
+            }
+            AF_INET => {
+                let socket_addr = netifa_addr as *mut sockaddr_in;
+                let internet_address = unsafe { (*socket_addr).sin_addr };
+                let index = netifa_index(&netifa);
+                let flags = make_interface_flags(netifa.ifa_flags);
+                let netmask = make_ipv4_netmask(&netifa);
+                let ip = ipv4_from_in_addr(&internet_address)?;
+                let (broadcast, destination) =
+                    split_broadcast_destination(flags, make_ipv4_dstaddr(&netifa)?);
+                // the record of an IPv4 address is named after its label
+                let label = AddrLabel::from_bytes(os_name.as_bytes());
+                NetworkInterface {
+                    name,
+                    os_name,
+                    addr: vec![Addr::V4(V4IfAddr {
+                        ip,
+                        broadcast,
+                        destination,
+                        netmask,
+                        label,
+                    })],
+                    mac_addr: None,
+                    index,
+                    flags,
+                    kind: InterfaceKind::Unknown,
+                    link: LinkDetails::default(),
+                }
+            }
+            AF_INET6 => {
+                let socket_addr = netifa_addr as *mut sockaddr_in6;
+                let internet_address = unsafe { (*socket_addr).sin6_addr };
+                let scope_id = unsafe { (*socket_addr).sin6_scope_id };
+                let index = netifa_index(&netifa);
+                let flags = make_interface_flags(netifa.ifa_flags);
+                let netmask = make_ipv6_netmask(&netifa);
+                let ip = ipv6_from_in6_addr(&internet_address)?;
+                let (broadcast, destination) =
+                    split_broadcast_destination(flags, make_ipv6_dstaddr(&netifa)?);
+                NetworkInterface {
+                    name,
+                    os_name,
+                    addr: vec![Addr::V6(V6IfAddr {
+                        ip,
+                        broadcast,
+                        destination,
+                        netmask,
+                        scope_id,
+                        flags: None,
+                        lifetimes: None,
+                    })],
+                    mac_addr: None,
+                    index,
+                    flags,
+                    kind: InterfaceKind::Unknown,
+                    link: LinkDetails::default(),
+                }
+            }
+            _ => continue,
+        };
+
+        // the interface was removed while walking the list
+        if network_interface.index == 0 {
+            continue;
+        }
+
+        let is_link = netifa_family == AF_PACKET;
+
+        network_interfaces
+            .entry(network_interface.index)
+            .and_modify(|old| {
+                old.addr.append(&mut network_interface.addr);
+
+                // the link record holds the name and hardware address of the
+                // interface itself
+                if is_link {
+                    old.name = network_interface.name.clone();
+                    old.os_name = network_interface.os_name.clone();
+                    old.mac_addr = network_interface.mac_addr;
+                }
+            })
+            .or_insert(network_interface);
+    }
+
+    let mut network_interfaces: Vec<_> = network_interfaces.into_values().collect();
+
+    for network_interface in &mut network_interfaces {
+        let addr = std::mem::take(&mut network_interface.addr)
+            .into_iter()
+            .map(|addr| adjust_address(network_interface, addr))
+            .collect();
+
+        network_interface.addr = addr;
+    }
+
+    query.retain(&mut network_interfaces);
+
+    // the kind is not provided by `getifaddrs`, unlike netlink, while the
+    // link layer details are left to `Sysfs::enrich`
+    let sysfs = Sysfs::new();
+
+    for network_interface in &mut network_interfaces {
+        network_interface.kind = sysfs.read_kind(
+            &network_interface.os_name,
+            network_interface.index,
+            network_interface.flags,
+        );
+    }
+
+    Ok(network_interfaces)
+}
+
+/// Retrieves the network interface name, which is not required to be valid
+/// UTF-8 by the kernel
+fn make_netifa_name(netifa: &libc::ifaddrs) -> OsString {
+    let name = unsafe { CStr::from_ptr(netifa.ifa_name) };
+
+    OsStr::from_bytes(name.to_bytes()).to_os_string()
+}
+
+/// Retrieves the broadcast or destination address for the network interface
+/// provided of the AF_INET family.
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man3/getifaddrs.3.html>
+fn make_ipv4_dstaddr(netifa: &libc::ifaddrs) -> Result<Option<Ipv4Addr>> {
+    let ifa_dstaddr = netifa.ifa_ifu;
+
+    if ifa_dstaddr.is_null() {
+        return Ok(None);
+    }
+
+    let socket_addr = ifa_dstaddr as *mut sockaddr_in;
+    let internet_address = unsafe { (*socket_addr).sin_addr };
+    let addr = ipv4_from_in_addr(&internet_address)?;
+
+    Ok(Some(addr))
+}
+
+/// Retrieves the broadcast or destination address for the network interface
+/// provided of the AF_INET6 family.
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man3/getifaddrs.3.html>
+fn make_ipv6_dstaddr(netifa: &libc::ifaddrs) -> Result<Option<Ipv6Addr>> {
+    let ifa_dstaddr = netifa.ifa_ifu;
+
+    if ifa_dstaddr.is_null() {
+        return Ok(None);
+    }
+
+    let socket_addr = ifa_dstaddr as *mut sockaddr_in6;
+    let internet_address = unsafe { (*socket_addr).sin6_addr };
+    let addr = ipv6_from_in6_addr(&internet_address)?;
+
+    Ok(Some(addr))
+}
+
+/// Retrieves the hardware address from the `sockaddr_ll` of an AF_PACKET
+/// network interface
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man7/packet.7.html>
+fn make_mac_addr(netifa: &libc::ifaddrs) -> Option<MacAddr> {
+    let netifa_addr = netifa.ifa_addr;
+    let socket_addr = netifa_addr as *mut sockaddr_ll;
+    let mac_array = unsafe { (*socket_addr).sll_addr };
+    let addr_len = unsafe { (*socket_addr).sll_halen };
+    let real_addr_len = std::cmp::min(addr_len as usize, mac_array.len());
+    let mac_slice = unsafe { std::slice::from_raw_parts(mac_array.as_ptr(), real_addr_len) };
+
+    MacAddr::new(mac_slice)
+}
+
+/// Retreives the index for the the network interface provided.
+///
+/// Records of IPv4 addresses with a label, such as `eth0:1`, are named after
+/// the label, in which case the index of the interface is retrieved from the
+/// name preceding the colon.
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man3/if_nametoindex.3.html>
+fn netifa_index(netifa: &libc::ifaddrs) -> u32 {
+    let name = netifa.ifa_name as *const libc::c_char;
+    let index = unsafe { if_nametoindex(name) };
+
+    if index != 0 {
+        return index;
+    }
+
+    let name = unsafe { CStr::from_ptr(name) }.to_bytes();
+
+    match name.iter().position(|b| *b == b':') {
+        Some(position) => match CString::new(&name[..position]) {
+            Ok(name) => unsafe { if_nametoindex(name.as_ptr()) },
+            Err(_) => 0,
+        },
+        None => 0,
+    }
+}
@@ -0,0 +1,107 @@
+mod ifaddrs;
+mod neighbor;
+mod netlink;
+mod netns;
+mod route;
+mod rtnetlink;
+mod stats;
+mod sysfs;
+mod watch;
+
+pub use sysfs::Sysfs;
+pub use watch::Watcher;
+
+pub(crate) use neighbor::neighbors;
+pub(crate) use route::routes;
+
+use std::net::IpAddr;
+
+use crate::{InterfaceQuery, MacAddr, NetworkInterface, NetworkInterfaceConfig, Result};
+
+use self::netlink::is_unavailable;
+
+/// Source used to retrieve the system's network interfaces on Linux and
+/// Android
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Dumps links and addresses from the kernel using `RTM_GETLINK` and
+    /// `RTM_GETADDR` requests over a `NETLINK_ROUTE` socket
+    #[default]
+    Netlink,
+    /// Walks the linked list provided by `getifaddrs`
+    GetIfAddrs,
+}
+
+impl NetworkInterfaceConfig for NetworkInterface {
+    fn show() -> Result<Vec<NetworkInterface>> {
+        match rtnetlink::show() {
+            // netlink sockets could be unavailable to the process, in such case
+            // fallback to `getifaddrs`
+            Err(error) if is_unavailable(&error) => ifaddrs::show(),
+            result => result,
+        }
+    }
+
+    fn by_name(name: &str) -> Result<Option<NetworkInterface>> {
+        fallback(rtnetlink::by_name(name), |network_interface| {
+            network_interface.name == name
+        })
+    }
+
+    fn by_index(index: u32) -> Result<Option<NetworkInterface>> {
+        fallback(rtnetlink::by_index(index), |network_interface| {
+            network_interface.index == index
+        })
+    }
+
+    fn by_mac(mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+        fallback(rtnetlink::by_mac(mac_addr), |network_interface| {
+            network_interface.mac_addr.as_ref() == Some(mac_addr)
+        })
+    }
+
+    fn by_ip(ip: IpAddr) -> Result<Option<NetworkInterface>> {
+        fallback(rtnetlink::by_ip(ip), |network_interface| {
+            network_interface.addr.iter().any(|addr| addr.ip() == ip)
+        })
+    }
+}
+
+/// Retrieves the network interfaces matching the provided query, same as
+/// `NetworkInterface::show` the `getifaddrs` backend is used if netlink
+/// sockets are not available
+pub(crate) fn query(query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+    match rtnetlink::query(query) {
+        Err(error) if is_unavailable(&error) => ifaddrs::query(query),
+        result => result,
+    }
+}
+
+/// Falls back to filtering the network interfaces retrieved with `getifaddrs`
+/// if a netlink lookup fails, same as `NetworkInterface::show`
+fn fallback<P>(
+    result: Result<Option<NetworkInterface>>,
+    predicate: P,
+) -> Result<Option<NetworkInterface>>
+where
+    P: Fn(&NetworkInterface) -> bool,
+{
+    match result {
+        Err(error) if is_unavailable(&error) => Ok(ifaddrs::show()?.into_iter().find(predicate)),
+        result => result,
+    }
+}
+
+impl NetworkInterface {
+    /// List system's network interfaces configuration using the provided
+    /// `Backend`.
+    ///
+    /// Unlike `NetworkInterface::show`, no fallback takes place if the
+    /// `Backend` fails.
+    pub fn show_with_backend(backend: Backend) -> Result<Vec<NetworkInterface>> {
+        match backend {
+            Backend::Netlink => rtnetlink::show(),
+            Backend::GetIfAddrs => ifaddrs::show(),
+        }
+    }
+}
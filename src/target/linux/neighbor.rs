@@ -0,0 +1,189 @@
+//! Neighbor tables retrieval through `RTM_GETNEIGH` dumps, falling back to
+//! the `/proc/net/arp` file for the IPv4 entries.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+//! <https://man7.org/linux/man-pages/man7/arp.7.html>
+use std::convert::TryInto;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use libc::{AF_INET, AF_INET6};
+
+use crate::{Error, MacAddr, Neighbor, NeighborFlags, NeighborState, Result};
+
+use super::netlink::{is_unavailable, read_i32, read_u16, Attributes, Message, NetlinkSocket};
+use super::route::name_to_index;
+
+pub const RTM_NEWNEIGH: u16 = 28;
+pub const RTM_GETNEIGH: u16 = 30;
+
+/// Size of `struct ndmsg`
+const NDMSG_LEN: usize = 12;
+
+const NDA_DST: u16 = 1;
+const NDA_LLADDR: u16 = 2;
+
+const PROC_NET_ARP: &str = "/proc/net/arp";
+
+const ATF_COM: u32 = 0x02;
+const ATF_PERM: u32 = 0x04;
+const ATF_PUBL: u32 = 0x08;
+
+/// Retrieves the entries of the IPv4 and IPv6 neighbor tables
+pub(crate) fn neighbors() -> Result<Vec<Neighbor>> {
+    match netlink_neighbors() {
+        // same as `NetworkInterface::show`, fallback to procfs if netlink
+        // sockets are not available
+        Err(error) if is_unavailable(&error) => {
+            let content =
+                fs::read_to_string(PROC_NET_ARP).map_err(|error| Error::ReadFileError {
+                    path: String::from(PROC_NET_ARP),
+                    source: error,
+                })?;
+
+            Ok(parse_proc_arp(&content, name_to_index))
+        }
+        result => result,
+    }
+}
+
+fn netlink_neighbors() -> Result<Vec<Neighbor>> {
+    let mut socket = NetlinkSocket::open()?;
+    let neighbors = socket
+        .dump(RTM_GETNEIGH, &[0; NDMSG_LEN])?
+        .iter()
+        .filter_map(parse_neighbor)
+        .collect();
+
+    Ok(neighbors)
+}
+
+/// Builds the neighbor described by a `RTM_NEWNEIGH` message payload, which
+/// starts with a `struct ndmsg`.
+fn parse_neighbor(message: &Message) -> Option<Neighbor> {
+    let payload = &message.payload;
+
+    if message.kind != RTM_NEWNEIGH || payload.len() < NDMSG_LEN {
+        return None;
+    }
+
+    let family = i32::from(payload[0]);
+    let mut ip = None;
+    let mut mac_addr = None;
+
+    for (kind, value) in Attributes::new(&payload[NDMSG_LEN..]) {
+        match (kind, family) {
+            (NDA_DST, AF_INET) => {
+                let octets: [u8; 4] = value.get(..4)?.try_into().ok()?;
+
+                ip = Some(IpAddr::from(Ipv4Addr::from(octets)));
+            }
+            (NDA_DST, AF_INET6) => {
+                let octets: [u8; 16] = value.get(..16)?.try_into().ok()?;
+
+                ip = Some(IpAddr::from(Ipv6Addr::from(octets)));
+            }
+            (NDA_LLADDR, _) => mac_addr = MacAddr::new(value),
+            _ => {}
+        }
+    }
+
+    Some(Neighbor {
+        // skips the entries of families other than IPv4 and IPv6
+        ip: ip?,
+        mac_addr,
+        index: read_i32(payload, 4)? as u32,
+        state: NeighborState::from(read_u16(payload, 8)?),
+        flags: NeighborFlags::from_bits(payload[10]),
+    })
+}
+
+/// Parses the IPv4 neighbors from `/proc/net/arp`.
+///
+/// The file only tells whether an entry is resolved, such entries are
+/// reported as `NeighborState::Reachable` unless they are permanent.
+fn parse_proc_arp<F>(content: &str, name_to_index: F) -> Vec<Neighbor>
+where
+    F: Fn(&str) -> u32,
+{
+    let mut neighbors = Vec::new();
+
+    // the first line holds the column names
+    for line in content.lines().skip(1) {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+
+        if fields.len() < 6 {
+            continue;
+        }
+
+        let ip = match fields[0].parse::<Ipv4Addr>() {
+            Ok(ip) => ip,
+            Err(_) => continue,
+        };
+        let flags = u32::from_str_radix(fields[2].trim_start_matches("0x"), 16).unwrap_or_default();
+        let state = if flags & ATF_PERM != 0 {
+            NeighborState::Permanent
+        } else if flags & ATF_COM != 0 {
+            NeighborState::Reachable
+        } else {
+            NeighborState::Incomplete
+        };
+
+        neighbors.push(Neighbor {
+            ip: ip.into(),
+            // unresolved entries are listed with a zeroed address
+            mac_addr: match flags & ATF_COM {
+                0 => None,
+                _ => fields[3].parse().ok(),
+            },
+            index: name_to_index(fields[5]),
+            state,
+            flags: if flags & ATF_PUBL != 0 {
+                NeighborFlags::PROXY
+            } else {
+                NeighborFlags::empty()
+            },
+        });
+    }
+
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{netlink_neighbors, parse_proc_arp};
+    use crate::{NeighborFlags, NeighborState};
+
+    #[test]
+    fn parses_proc_net_arp() {
+        let content = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.0.2.1        0x1         0x2         02:fc:00:00:00:05     *        eth0
+192.0.2.10       0x1         0x6         02:00:00:00:00:01     *        eth0
+192.0.2.11       0x1         0x0         00:00:00:00:00:00     *        eth0
+";
+        let neighbors = parse_proc_arp(content, |name| if name == "eth0" { 2 } else { 0 });
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0].ip.to_string(), "192.0.2.1");
+        assert_eq!(
+            neighbors[0].mac_addr.map(|mac_addr| mac_addr.to_string()),
+            Some(String::from("02:fc:00:00:00:05"))
+        );
+        assert_eq!(neighbors[0].index, 2);
+        assert_eq!(neighbors[0].state, NeighborState::Reachable);
+        assert_eq!(neighbors[0].flags, NeighborFlags::empty());
+        assert_eq!(neighbors[1].state, NeighborState::Permanent);
+        assert_eq!(neighbors[2].mac_addr, None);
+        assert_eq!(neighbors[2].state, NeighborState::Incomplete);
+    }
+
+    #[test]
+    fn netlink_neighbors_are_valid() {
+        for neighbor in netlink_neighbors().unwrap() {
+            assert_ne!(neighbor.index, 0, "{:?}", neighbor);
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! Minimal `NETLINK_ROUTE` transport used to query the kernel routing
+//! subsystem (rtnetlink) without relying on `getifaddrs`.
+//!
+//! Message and attribute layouts are parsed from raw bytes in native endian
+//! as described in the references below, so only the socket related symbols
+//! are taken from `libc`.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/netlink.7.html>
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use libc::{
+    sockaddr_nl, AF_NETLINK, EACCES, EAFNOSUPPORT, EAGAIN, EPERM, EPROTONOSUPPORT, MSG_DONTWAIT,
+    MSG_PEEK, MSG_TRUNC, NETLINK_ROUTE, SOCK_CLOEXEC, SOCK_RAW,
+};
+
+use crate::{Error, Result};
+
+/// Size of `struct nlmsghdr`
+pub const NLMSG_HDRLEN: usize = 16;
+
+/// Size of `struct rtattr` header
+const RTA_HDRLEN: usize = 4;
+
+pub const NLM_F_REQUEST: u16 = 0x01;
+pub const NLM_F_MULTI: u16 = 0x02;
+pub const NLM_F_DUMP: u16 = 0x300;
+
+pub const NLMSG_ERROR: u16 = 0x02;
+pub const NLMSG_DONE: u16 = 0x03;
+
+pub const RTM_NEWLINK: u16 = 16;
+pub const RTM_DELLINK: u16 = 17;
+pub const RTM_GETLINK: u16 = 18;
+pub const RTM_NEWADDR: u16 = 20;
+pub const RTM_DELADDR: u16 = 21;
+pub const RTM_GETADDR: u16 = 22;
+
+pub const RTMGRP_LINK: u32 = 0x01;
+pub const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+pub const RTMGRP_IPV6_IFADDR: u32 = 0x100;
+
+/// `SOL_NETLINK` socket level and `NETLINK_GET_STRICT_CHK` option, which
+/// enables the filtering of dump requests on the header fields
+const SOL_NETLINK: libc::c_int = 270;
+const NETLINK_GET_STRICT_CHK: libc::c_int = 12;
+
+/// Netlink attributes may have the nested and byte order bits set on their
+/// type, which are not part of the attribute identifier.
+const NLA_TYPE_MASK: u16 = !(1 << 15 | 1 << 14);
+
+/// Rounds `len` up to the 4 bytes alignment used by netlink messages and
+/// attributes
+pub const fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// A netlink message received from the kernel
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// The `nlmsg_type` field, e.g. `RTM_NEWLINK`
+    pub kind: u16,
+    /// Message content following the `nlmsghdr`
+    pub payload: Vec<u8>,
+}
+
+/// A `NETLINK_ROUTE` socket bound to a kernel assigned port
+pub struct NetlinkSocket {
+    fd: RawFd,
+    seq: u32,
+    buffer: Vec<u8>,
+}
+
+impl NetlinkSocket {
+    /// Opens a `NETLINK_ROUTE` socket
+    ///
+    /// ## References
+    ///
+    /// <https://man7.org/linux/man-pages/man7/netlink.7.html>
+    pub fn open() -> Result<Self> {
+        NetlinkSocket::open_with_groups(0)
+    }
+
+    /// Opens a `NETLINK_ROUTE` socket subscribed to the provided `RTMGRP_*`
+    /// multicast groups
+    ///
+    /// ## References
+    ///
+    /// <https://man7.org/linux/man-pages/man7/netlink.7.html>
+    pub fn open_with_groups(groups: u32) -> Result<Self> {
+        let fd = unsafe { libc::socket(AF_NETLINK, SOCK_RAW | SOCK_CLOEXEC, NETLINK_ROUTE) };
+
+        if fd < 0 {
+            return Err(last_netlink_error("socket"));
+        }
+
+        let socket = NetlinkSocket {
+            fd,
+            seq: 0,
+            buffer: vec![0; 8192],
+        };
+
+        let mut addr: sockaddr_nl = unsafe { mem::zeroed() };
+        addr.nl_family = AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = groups;
+
+        let bind_result = unsafe {
+            libc::bind(
+                socket.fd,
+                &addr as *const sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+
+        if bind_result < 0 {
+            return Err(last_netlink_error("bind"));
+        }
+
+        Ok(socket)
+    }
+
+    /// Asks the kernel to validate requests strictly and to filter dumps on
+    /// the provided header fields, such as the interface index.
+    ///
+    /// Kernels before 4.20 lack this option and fail with `ENOPROTOOPT`, in
+    /// which case dumps are not filtered and the replies must be filtered by
+    /// the caller.
+    pub fn enable_strict_checking(&self) -> io::Result<()> {
+        let enable: libc::c_int = 1;
+
+        let result = unsafe {
+            libc::setsockopt(
+                self.fd,
+                SOL_NETLINK,
+                NETLINK_GET_STRICT_CHK,
+                &enable as *const libc::c_int as *const libc::c_void,
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Sends a `NLM_F_DUMP` request of the provided `kind` and collects every
+    /// message the kernel replies with until `NLMSG_DONE` is received.
+    pub fn dump(&mut self, kind: u16, payload: &[u8]) -> Result<Vec<Message>> {
+        self.request(kind, NLM_F_REQUEST | NLM_F_DUMP, payload)
+    }
+
+    /// Sends a request and collects the replies to it.
+    ///
+    /// Replies are gathered until `NLMSG_DONE` is received, or until a
+    /// datagram holding a message without the `NLM_F_MULTI` flag is fully
+    /// consumed for non-dump requests.
+    pub fn request(&mut self, kind: u16, flags: u16, payload: &[u8]) -> Result<Vec<Message>> {
+        self.seq = self.seq.wrapping_add(1);
+        let seq = self.seq;
+        let request = encode_message(kind, flags, seq, payload);
+
+        let mut addr: sockaddr_nl = unsafe { mem::zeroed() };
+        addr.nl_family = AF_NETLINK as libc::sa_family_t;
+
+        let sent = unsafe {
+            libc::sendto(
+                self.fd,
+                request.as_ptr() as *const libc::c_void,
+                request.len(),
+                0,
+                &addr as *const sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+
+        if sent < 0 {
+            return Err(last_netlink_error("sendto"));
+        }
+
+        let mut messages = Vec::new();
+
+        loop {
+            let len = self.recv(0)?;
+            let mut done = false;
+
+            for message in Messages::new(&self.buffer[..len]) {
+                let (header, content) = message?;
+
+                if header.seq != seq {
+                    continue;
+                }
+
+                match header.kind {
+                    NLMSG_DONE => done = true,
+                    NLMSG_ERROR => {
+                        let errno = read_i32(content, 0).unwrap_or(0);
+
+                        if errno == 0 {
+                            // acknowledgment
+                            done = true;
+                        } else {
+                            return Err(Error::netlink("request", -errno));
+                        }
+                    }
+                    kind => {
+                        if header.flags & NLM_F_MULTI == 0 {
+                            done = true;
+                        }
+
+                        messages.push(Message {
+                            kind,
+                            payload: content.to_vec(),
+                        });
+                    }
+                }
+            }
+
+            if done {
+                return Ok(messages);
+            }
+        }
+    }
+
+    /// Receives the messages of a single datagram sent to the subscribed
+    /// multicast groups.
+    ///
+    /// Returns `None` without waiting if `nonblocking` is set and no datagram
+    /// is available, as well as when the socket is in non-blocking mode.
+    pub fn recv_multicast(&mut self, nonblocking: bool) -> Result<Option<Vec<Message>>> {
+        let flags = if nonblocking { MSG_DONTWAIT } else { 0 };
+        let len = match self.recv(flags) {
+            Ok(len) => len,
+            Err(error) if error.raw_os_error() == Some(EAGAIN) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let mut messages = Vec::new();
+
+        for message in Messages::new(&self.buffer[..len]) {
+            let (header, content) = message?;
+
+            if header.kind == NLMSG_DONE || header.kind == NLMSG_ERROR {
+                continue;
+            }
+
+            messages.push(Message {
+                kind: header.kind,
+                payload: content.to_vec(),
+            });
+        }
+
+        Ok(Some(messages))
+    }
+
+    /// Sets the `O_NONBLOCK` flag on the socket
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        let flags = unsafe { libc::fcntl(self.fd, libc::F_GETFL) };
+
+        if flags < 0 {
+            return Err(last_netlink_error("fcntl"));
+        }
+
+        let flags = if nonblocking {
+            flags | libc::O_NONBLOCK
+        } else {
+            flags & !libc::O_NONBLOCK
+        };
+
+        if unsafe { libc::fcntl(self.fd, libc::F_SETFL, flags) } < 0 {
+            return Err(last_netlink_error("fcntl"));
+        }
+
+        Ok(())
+    }
+
+    /// Receives a single datagram into the internal buffer, growing it if
+    /// needed, and returns the amount of bytes received.
+    fn recv(&mut self, flags: libc::c_int) -> Result<usize> {
+        loop {
+            // peek the datagram size to avoid truncating large messages
+            let len = unsafe {
+                libc::recv(
+                    self.fd,
+                    self.buffer.as_mut_ptr() as *mut libc::c_void,
+                    self.buffer.len(),
+                    flags | MSG_PEEK | MSG_TRUNC,
+                )
+            };
+
+            if len < 0 {
+                if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+
+                return Err(last_netlink_error("recv"));
+            }
+
+            if len as usize > self.buffer.len() {
+                self.buffer.resize(len as usize, 0);
+            }
+
+            let len = unsafe {
+                libc::recv(
+                    self.fd,
+                    self.buffer.as_mut_ptr() as *mut libc::c_void,
+                    self.buffer.len(),
+                    flags,
+                )
+            };
+
+            if len < 0 {
+                if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+
+                return Err(last_netlink_error("recv"));
+            }
+
+            return Ok(len as usize);
+        }
+    }
+}
+
+impl AsRawFd for NetlinkSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+/// Fields of a `struct nlmsghdr`
+#[derive(Debug, Clone, Copy)]
+pub struct MessageHeader {
+    pub kind: u16,
+    pub flags: u16,
+    pub seq: u32,
+}
+
+/// Iterator over the netlink messages contained on a single datagram
+pub struct Messages<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Messages<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Messages { data }
+    }
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = Result<(MessageHeader, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < NLMSG_HDRLEN {
+            return None;
+        }
+
+        let len = read_u32(self.data, 0)? as usize;
+
+        if len < NLMSG_HDRLEN || len > self.data.len() {
+            self.data = &[];
+            return Some(Err(Error::netlink("recv", libc::EBADMSG)));
+        }
+
+        let header = MessageHeader {
+            kind: read_u16(self.data, 4)?,
+            flags: read_u16(self.data, 6)?,
+            seq: read_u32(self.data, 8)?,
+        };
+        let content = &self.data[NLMSG_HDRLEN..len];
+
+        self.data = &self.data[std::cmp::min(align(len), self.data.len())..];
+
+        Some(Ok((header, content)))
+    }
+}
+
+/// Iterator over the route attributes (`struct rtattr`) on a message payload
+/// yielding the attribute type alongside its data
+pub struct Attributes<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Attributes<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Attributes { data }
+    }
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = (u16, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < RTA_HDRLEN {
+            return None;
+        }
+
+        let len = read_u16(self.data, 0)? as usize;
+        let kind = read_u16(self.data, 2)? & NLA_TYPE_MASK;
+
+        if len < RTA_HDRLEN || len > self.data.len() {
+            self.data = &[];
+            return None;
+        }
+
+        let value = &self.data[RTA_HDRLEN..len];
+
+        self.data = &self.data[std::cmp::min(align(len), self.data.len())..];
+
+        Some((kind, value))
+    }
+}
+
+/// Encodes a netlink message with the provided payload
+pub fn encode_message(kind: u16, flags: u16, seq: u32, payload: &[u8]) -> Vec<u8> {
+    let len = NLMSG_HDRLEN + payload.len();
+    let mut message = Vec::with_capacity(align(len));
+
+    message.extend_from_slice(&(len as u32).to_ne_bytes());
+    message.extend_from_slice(&kind.to_ne_bytes());
+    message.extend_from_slice(&flags.to_ne_bytes());
+    message.extend_from_slice(&seq.to_ne_bytes());
+    // let the kernel fill the port id
+    message.extend_from_slice(&0u32.to_ne_bytes());
+    message.extend_from_slice(payload);
+    message.resize(align(len), 0);
+
+    message
+}
+
+/// Encodes a route attribute with the provided value
+pub fn encode_attribute(kind: u16, value: &[u8]) -> Vec<u8> {
+    let len = RTA_HDRLEN + value.len();
+    let mut attribute = Vec::with_capacity(align(len));
+
+    attribute.extend_from_slice(&(len as u16).to_ne_bytes());
+    attribute.extend_from_slice(&kind.to_ne_bytes());
+    attribute.extend_from_slice(value);
+    attribute.resize(align(len), 0);
+
+    attribute
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+
+    Some(u16::from_ne_bytes([bytes[0], bytes[1]]))
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+
+    Some(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+pub fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    read_u32(data, offset).map(|value| value as i32)
+}
+
+/// Reads a NUL terminated string attribute which is not required to be valid
+/// UTF-8, such as an interface name
+pub fn read_os_string(data: &[u8]) -> OsString {
+    let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+
+    OsStr::from_bytes(&data[..len]).to_os_string()
+}
+
+/// Returns `true` if the error was raised while opening a netlink socket
+/// which is not available to the process, for instance when restricted by
+/// seccomp filters or SELinux policies on Android.
+///
+/// Other sources may be used in such case, while the failures of the requests
+/// themselves are reported as is.
+pub fn is_unavailable(error: &Error) -> bool {
+    match error {
+        Error::NetlinkError {
+            operation, source, ..
+        } => {
+            matches!(*operation, "socket" | "bind")
+                && matches!(
+                    source.raw_os_error(),
+                    Some(EPERM | EACCES | EAFNOSUPPORT | EPROTONOSUPPORT)
+                )
+        }
+        _ => false,
+    }
+}
+
+fn last_netlink_error(operation: &'static str) -> Error {
+    Error::NetlinkError {
+        operation,
+        interface: None,
+        source: io::Error::last_os_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tells_unavailable_sockets_apart() {
+        assert!(is_unavailable(&Error::netlink("socket", EAFNOSUPPORT)));
+        assert!(is_unavailable(&Error::netlink("bind", EACCES)));
+        assert!(!is_unavailable(&Error::netlink("sendto", EPERM)));
+        assert!(!is_unavailable(&Error::netlink("recv", libc::EBADMSG)));
+        assert!(!is_unavailable(&Error::netlink("recv", libc::EINVAL)));
+    }
+
+    #[test]
+    fn parses_messages_and_attributes() {
+        let mut payload = Vec::new();
+        payload.extend(encode_attribute(3, b"eth0\0"));
+        payload.extend(encode_attribute(1 | 1 << 15, &[1, 2, 3, 4, 5, 6]));
+
+        let mut datagram = encode_message(RTM_NEWLINK, NLM_F_MULTI, 7, &payload);
+        datagram.extend(encode_message(NLMSG_DONE, NLM_F_MULTI, 7, &[0; 4]));
+
+        let messages = Messages::new(&datagram)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0.kind, RTM_NEWLINK);
+        assert_eq!(messages[0].0.seq, 7);
+        assert_eq!(messages[1].0.kind, NLMSG_DONE);
+
+        let attributes = Attributes::new(messages[0].1).collect::<Vec<_>>();
+
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(read_os_string(attributes[0].1), "eth0");
+        assert_eq!(attributes[1], (1, &[1, 2, 3, 4, 5, 6][..]));
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let mut datagram = encode_message(RTM_NEWLINK, 0, 1, &[0; 8]);
+        datagram.truncate(20);
+
+        assert!(Messages::new(&datagram).next().unwrap().is_err());
+    }
+}
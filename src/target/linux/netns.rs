@@ -0,0 +1,81 @@
+//! Network interfaces retrieval inside another network namespace, entered
+//! by a helper thread so the namespace of the calling thread is preserved.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man2/setns.2.html>
+//! <https://man7.org/linux/man-pages/man7/network_namespaces.7.html>
+use std::fs::{self, File};
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use libc::{setns, CLONE_NEWNET};
+
+use crate::{Error, NetworkInterface, NetworkInterfaceConfig, Result};
+
+impl NetworkInterface {
+    /// List the network interfaces of the network namespace referred to by
+    /// the provided path, such as `/var/run/netns/NAME` for the namespaces
+    /// created by `ip netns` or `/proc/<pid>/ns/net` for the namespace of a
+    /// process.
+    ///
+    /// Entering a network namespace requires the `CAP_SYS_ADMIN` capability.
+    ///
+    /// The retrieved interfaces cannot be completed with `Sysfs::enrich`, as
+    /// `/sys` lists the interfaces of the namespace it was mounted from.
+    pub fn show_in_netns<P: AsRef<Path>>(path: P) -> Result<Vec<NetworkInterface>> {
+        in_netns(path.as_ref(), NetworkInterface::show)
+    }
+}
+
+/// Runs `f` on a helper thread moved into the network namespace referred to
+/// by the provided path, resuming its panic if any
+fn in_netns<F, T>(path: &Path, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let name = path.display().to_string();
+    let netns = File::open(path).map_err(|error| Error::ReadFileError {
+        path: name.clone(),
+        source: error,
+    })?;
+
+    // `setns` only moves the calling thread, the helper thread exits with
+    // the namespace once `f` returns
+    let handle = std::thread::spawn(move || {
+        if unsafe { setns(netns.as_raw_fd(), CLONE_NEWNET) } != 0 {
+            return Err(Error::SetNsError {
+                path: name,
+                source: io::Error::last_os_error(),
+            });
+        }
+
+        f()
+    });
+
+    match handle.join() {
+        Ok(result) => result,
+        Err(panic) => std::panic::resume_unwind(panic),
+    }
+}
+
+/// Returns `true` if the calling thread is in the network namespace of the
+/// process, unlike the helper threads of `in_netns`.
+///
+/// The interfaces listed on sysfs are the ones of the namespace it was
+/// mounted from, which is usually the one of the process.
+pub(super) fn in_process_netns() -> bool {
+    let netns = |path: &str| fs::metadata(path).map(|metadata| (metadata.dev(), metadata.ino()));
+
+    match (
+        netns("/proc/thread-self/ns/net"),
+        netns("/proc/self/ns/net"),
+    ) {
+        (Ok(thread), Ok(process)) => thread == process,
+        // namespaces cannot be told apart, e.g. before Linux 3.17
+        _ => true,
+    }
+}
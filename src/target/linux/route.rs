@@ -0,0 +1,413 @@
+//! Routing tables retrieval through `RTM_GETROUTE` dumps, falling back to
+//! the `/proc/net/route` and `/proc/net/ipv6_route` files.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+//! <https://man7.org/linux/man-pages/man5/proc.5.html>
+use std::convert::TryInto;
+use std::ffi::CString;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use libc::{if_nametoindex, AF_INET, AF_INET6};
+
+use crate::{AddrScope, Error, Result, Route, RouteKind, RouteProtocol, LOCAL_TABLE, MAIN_TABLE};
+
+use super::netlink::{is_unavailable, read_u16, read_u32, Attributes, Message, NetlinkSocket};
+
+pub const RTM_NEWROUTE: u16 = 24;
+pub const RTM_GETROUTE: u16 = 26;
+
+/// Size of `struct rtmsg`
+const RTMSG_LEN: usize = 12;
+
+/// Size of `struct rtnexthop`
+const RTNEXTHOP_LEN: usize = 8;
+
+const RTA_DST: u16 = 1;
+const RTA_OIF: u16 = 4;
+const RTA_GATEWAY: u16 = 5;
+const RTA_PRIORITY: u16 = 6;
+const RTA_PREFSRC: u16 = 7;
+const RTA_MULTIPATH: u16 = 9;
+const RTA_TABLE: u16 = 15;
+
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+const PROC_NET_IPV6_ROUTE: &str = "/proc/net/ipv6_route";
+
+const RTF_GATEWAY: u32 = 0x0002;
+const RTF_REJECT: u32 = 0x0200;
+const RTF_ADDRCONF: u32 = 0x0004_0000;
+const RTF_LOCAL: u32 = 0x8000_0000;
+
+/// Retrieves the routes of every routing table
+pub(crate) fn routes() -> Result<Vec<Route>> {
+    match netlink_routes() {
+        // same as `NetworkInterface::show`, fallback to procfs if netlink
+        // sockets are not available
+        Err(error) if is_unavailable(&error) => proc_routes(),
+        result => result,
+    }
+}
+
+fn netlink_routes() -> Result<Vec<Route>> {
+    let mut socket = NetlinkSocket::open()?;
+    let mut routes = Vec::new();
+
+    for message in socket.dump(RTM_GETROUTE, &[0; RTMSG_LEN])? {
+        routes.extend(parse_route(&message));
+    }
+
+    Ok(routes)
+}
+
+/// Builds the routes described by a `RTM_NEWROUTE` message payload, which
+/// starts with a `struct rtmsg`.
+///
+/// Multipath routes are reported as a route for each next hop.
+fn parse_route(message: &Message) -> Vec<Route> {
+    let payload = &message.payload;
+
+    if message.kind != RTM_NEWROUTE || payload.len() < RTMSG_LEN {
+        return Vec::new();
+    }
+
+    let family = i32::from(payload[0]);
+    let parse_ip = |bytes: &[u8]| -> Option<IpAddr> {
+        match family {
+            AF_INET => {
+                let octets: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+                Some(Ipv4Addr::from(octets).into())
+            }
+            AF_INET6 => {
+                let octets: [u8; 16] = bytes.get(..16)?.try_into().ok()?;
+                Some(Ipv6Addr::from(octets).into())
+            }
+            _ => None,
+        }
+    };
+    let unspecified = match family {
+        AF_INET => IpAddr::from(Ipv4Addr::UNSPECIFIED),
+        AF_INET6 => IpAddr::from(Ipv6Addr::UNSPECIFIED),
+        _ => return Vec::new(),
+    };
+
+    let mut route = Route {
+        destination: unspecified,
+        prefix_len: payload[1],
+        gateway: None,
+        index: 0,
+        metric: 0,
+        table: u32::from(payload[4]),
+        protocol: RouteProtocol::from(payload[5]),
+        scope: make_route_scope(payload[6]),
+        source: None,
+        kind: RouteKind::from(payload[7]),
+    };
+    let mut multipath = None;
+
+    for (kind, value) in Attributes::new(&payload[RTMSG_LEN..]) {
+        match kind {
+            RTA_DST => route.destination = parse_ip(value).unwrap_or(unspecified),
+            RTA_OIF => route.index = read_u32(value, 0).unwrap_or_default(),
+            RTA_GATEWAY => route.gateway = parse_ip(value),
+            RTA_PRIORITY => route.metric = read_u32(value, 0).unwrap_or_default(),
+            RTA_PREFSRC => route.source = parse_ip(value),
+            RTA_TABLE => route.table = read_u32(value, 0).unwrap_or(route.table),
+            RTA_MULTIPATH => multipath = Some(value),
+            _ => {}
+        }
+    }
+
+    let mut nexthops = match multipath {
+        Some(multipath) => multipath,
+        None => return vec![route],
+    };
+    let mut routes = Vec::new();
+
+    // a `struct rtnexthop` followed by its attributes for each next hop
+    while nexthops.len() >= RTNEXTHOP_LEN {
+        let len = read_u16(nexthops, 0).unwrap_or_default() as usize;
+
+        if len < RTNEXTHOP_LEN || len > nexthops.len() {
+            break;
+        }
+
+        let mut nexthop = route.clone();
+
+        nexthop.index = read_u32(nexthops, 4).unwrap_or_default();
+
+        for (kind, value) in Attributes::new(&nexthops[RTNEXTHOP_LEN..len]) {
+            if kind == RTA_GATEWAY {
+                nexthop.gateway = parse_ip(value);
+            }
+        }
+
+        routes.push(nexthop);
+        nexthops = &nexthops[std::cmp::min(super::netlink::align(len), nexthops.len())..];
+    }
+
+    routes
+}
+
+/// Maps a `RT_SCOPE_*` number to the `AddrScope` covering it, as the kernel
+/// defines the scopes as ranges between universe and host
+fn make_route_scope(scope: u8) -> AddrScope {
+    match scope {
+        0..=199 => AddrScope::Global,
+        200..=252 => AddrScope::Site,
+        253 => AddrScope::Link,
+        _ => AddrScope::Host,
+    }
+}
+
+fn proc_routes() -> Result<Vec<Route>> {
+    let read = |path: &str| {
+        fs::read_to_string(path).map_err(|error| Error::ReadFileError {
+            path: String::from(path),
+            source: error,
+        })
+    };
+    let mut routes = parse_proc_route(&read(PROC_NET_ROUTE)?, name_to_index);
+
+    // IPv6 could be disabled
+    if let Ok(content) = read(PROC_NET_IPV6_ROUTE) {
+        routes.extend(parse_proc_ipv6_route(&content, name_to_index));
+    }
+
+    Ok(routes)
+}
+
+pub(super) fn name_to_index(name: &str) -> u32 {
+    match CString::new(name) {
+        Ok(name) => unsafe { if_nametoindex(name.as_ptr()) },
+        Err(_) => 0,
+    }
+}
+
+/// Parses the IPv4 routes of the main table from `/proc/net/route`, where
+/// addresses are printed as hexadecimal integers in host byte order.
+///
+/// The file provides neither the protocol nor the scope of the routes, which
+/// are guessed from the presence of a gateway.
+fn parse_proc_route<F>(content: &str, name_to_index: F) -> Vec<Route>
+where
+    F: Fn(&str) -> u32,
+{
+    let parse_ip = |hex: &str| {
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|value| IpAddr::from(Ipv4Addr::from(value.to_ne_bytes())))
+    };
+    let mut routes = Vec::new();
+
+    // the first line holds the column names
+    for line in content.lines().skip(1) {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+
+        if fields.len() < 8 {
+            continue;
+        }
+
+        let (destination, gateway, netmask) = match (
+            parse_ip(fields[1]),
+            parse_ip(fields[2]),
+            parse_ip(fields[7]),
+        ) {
+            (Some(destination), Some(gateway), Some(IpAddr::V4(netmask))) => {
+                (destination, gateway, netmask)
+            }
+            _ => continue,
+        };
+        let flags = u32::from_str_radix(fields[3], 16).unwrap_or_default();
+        let gateway = if flags & RTF_GATEWAY != 0 {
+            Some(gateway)
+        } else {
+            None
+        };
+
+        routes.push(Route {
+            destination,
+            prefix_len: u32::from(netmask).count_ones() as u8,
+            gateway,
+            index: name_to_index(fields[0]),
+            metric: fields[6].parse().unwrap_or_default(),
+            table: MAIN_TABLE,
+            protocol: RouteProtocol::Unspec,
+            scope: if gateway.is_some() {
+                AddrScope::Global
+            } else {
+                AddrScope::Link
+            },
+            source: None,
+            kind: if flags & RTF_REJECT != 0 {
+                RouteKind::Unreachable
+            } else {
+                RouteKind::Unicast
+            },
+        });
+    }
+
+    routes
+}
+
+/// Parses the IPv6 routes from `/proc/net/ipv6_route`, where addresses are
+/// printed as 32 hexadecimal digits and numbers in hexadecimal.
+///
+/// The file lists the routes of every table without identifying it, the
+/// local routes are reported on `LOCAL_TABLE` and the rest on `MAIN_TABLE`.
+fn parse_proc_ipv6_route<F>(content: &str, name_to_index: F) -> Vec<Route>
+where
+    F: Fn(&str) -> u32,
+{
+    let parse_ip = |hex: &str| u128::from_str_radix(hex, 16).ok().map(Ipv6Addr::from);
+    let parse_hex = |hex: &str| u32::from_str_radix(hex, 16).ok();
+    let mut routes = Vec::new();
+
+    for line in content.lines() {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+
+        if fields.len() < 10 {
+            continue;
+        }
+
+        let (destination, prefix_len, gateway, metric, flags) = match (
+            parse_ip(fields[0]),
+            parse_hex(fields[1]),
+            parse_ip(fields[4]),
+            parse_hex(fields[5]),
+            parse_hex(fields[8]),
+        ) {
+            (Some(destination), Some(prefix_len), Some(gateway), Some(metric), Some(flags)) => {
+                (destination, prefix_len, gateway, metric, flags)
+            }
+            _ => continue,
+        };
+
+        // the kernel lists its placeholder for unreachable destinations
+        if flags & RTF_REJECT != 0 && metric == u32::MAX {
+            continue;
+        }
+
+        let is_local = flags & RTF_LOCAL != 0;
+        let gateway = if flags & RTF_GATEWAY != 0 {
+            Some(IpAddr::from(gateway))
+        } else {
+            None
+        };
+
+        routes.push(Route {
+            destination: destination.into(),
+            prefix_len: prefix_len as u8,
+            gateway,
+            index: name_to_index(fields[9]),
+            metric,
+            table: if is_local { LOCAL_TABLE } else { MAIN_TABLE },
+            protocol: if flags & RTF_ADDRCONF != 0 {
+                RouteProtocol::Ra
+            } else {
+                RouteProtocol::Unspec
+            },
+            scope: if is_local {
+                AddrScope::Host
+            } else if gateway.is_some() {
+                AddrScope::Global
+            } else {
+                AddrScope::Link
+            },
+            source: None,
+            kind: if is_local {
+                RouteKind::Local
+            } else if flags & RTF_REJECT != 0 {
+                RouteKind::Unreachable
+            } else {
+                RouteKind::Unicast
+            },
+        });
+    }
+
+    routes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{netlink_routes, parse_proc_ipv6_route, parse_proc_route, proc_routes};
+    use crate::{AddrScope, RouteKind, LOCAL_TABLE, MAIN_TABLE};
+
+    fn name_to_index(name: &str) -> u32 {
+        match name {
+            "lo" => 1,
+            "eth0" => 2,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn parses_proc_net_route() {
+        let content = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t010200C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t000200C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0
+";
+        let routes = parse_proc_route(content, name_to_index);
+
+        assert_eq!(routes.len(), 2);
+        assert!(routes[0].is_default());
+        assert_eq!(routes[0].gateway, Some("192.0.2.1".parse().unwrap()));
+        assert_eq!(routes[0].metric, 100);
+        assert_eq!(routes[0].index, 2);
+        assert_eq!(
+            routes[1].destination,
+            "192.0.2.0".parse::<std::net::IpAddr>().unwrap()
+        );
+        assert_eq!(routes[1].prefix_len, 24);
+        assert_eq!(routes[1].gateway, None);
+        assert_eq!(routes[1].scope, AddrScope::Link);
+    }
+
+    #[test]
+    fn parses_proc_net_ipv6_route() {
+        let content = "\
+fd000000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fd000000000000000000000000000001 00000400 00000001 00000000 00000003     eth0
+00000000000000000000000000000001 80 00000000000000000000000000000000 00 00000000000000000000000000000000 00000000 00000002 00000000 80200001       lo
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200       lo
+";
+        let routes = parse_proc_ipv6_route(content, name_to_index);
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].prefix_len, 64);
+        assert_eq!(routes[0].metric, 256);
+        assert_eq!(routes[0].table, MAIN_TABLE);
+        assert!(routes[1].is_default());
+        assert_eq!(routes[1].gateway, Some("fd00::1".parse().unwrap()));
+        assert_eq!(routes[1].metric, 1024);
+        assert_eq!(routes[2].index, 1);
+        assert_eq!(routes[2].table, LOCAL_TABLE);
+        assert_eq!(routes[2].kind, RouteKind::Local);
+    }
+
+    #[test]
+    fn netlink_and_procfs_routes_match() {
+        let netlink = netlink_routes().unwrap();
+
+        for route in proc_routes().unwrap() {
+            if route.table != MAIN_TABLE || route.kind != RouteKind::Unicast {
+                continue;
+            }
+
+            assert!(
+                netlink
+                    .iter()
+                    .any(|other| other.destination == route.destination
+                        && other.prefix_len == route.prefix_len
+                        && other.gateway == route.gateway
+                        && other.index == route.index
+                        && other.metric == route.metric),
+                "{:?}",
+                route
+            );
+        }
+    }
+}
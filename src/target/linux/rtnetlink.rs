@@ -0,0 +1,691 @@
+//! Network interfaces retrieval through `RTM_GETLINK` and `RTM_GETADDR`
+//! dumps over a `NETLINK_ROUTE` socket.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::ffi::OsStrExt;
+use std::time::Duration;
+
+use libc::{AF_BRIDGE, AF_INET, AF_INET6, ENODEV, IFNAMSIZ};
+
+use crate::interface::ipv6_scope;
+use crate::subnet::{ipv4_netmask, ipv6_netmask};
+use crate::{
+    Addr, AddrFlags, AddrLabel, AddrLifetimes, AddrScope, Family, InterfaceId, InterfaceKind,
+    InterfaceQuery, LinkDetails, LinkMode, MacAddr, MacvlanMode, NetworkInterface, OperState,
+    Result, V4IfAddr, V6IfAddr, VlanProtocol,
+};
+use crate::utils::make_interface_flags;
+
+use super::netlink::{
+    encode_attribute, read_os_string, read_u16, read_u32, Attributes, Message, NetlinkSocket,
+    NLM_F_REQUEST, RTM_GETADDR, RTM_GETLINK, RTM_NEWADDR, RTM_NEWLINK,
+};
+
+/// Size of `struct ifinfomsg`
+pub(super) const IFINFOMSG_LEN: usize = 16;
+
+/// Size of `struct ifaddrmsg`
+const IFADDRMSG_LEN: usize = 8;
+
+const IFLA_ADDRESS: u16 = 1;
+const IFLA_IFNAME: u16 = 3;
+const IFLA_MTU: u16 = 4;
+const IFLA_OPERSTATE: u16 = 16;
+const IFLA_LINKMODE: u16 = 17;
+const IFLA_LINKINFO: u16 = 18;
+const IFLA_CARRIER: u16 = 33;
+const IFLA_CARRIER_UP_COUNT: u16 = 47;
+const IFLA_CARRIER_DOWN_COUNT: u16 = 48;
+
+const IFLA_INFO_KIND: u16 = 1;
+const IFLA_INFO_DATA: u16 = 2;
+
+const IFLA_VLAN_ID: u16 = 1;
+const IFLA_VLAN_PROTOCOL: u16 = 5;
+const IFLA_MACVLAN_MODE: u16 = 1;
+
+const ARPHRD_ETHER: u16 = 1;
+
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+const IFA_LABEL: u16 = 3;
+const IFA_BROADCAST: u16 = 4;
+const IFA_CACHEINFO: u16 = 6;
+const IFA_FLAGS: u16 = 8;
+
+/// Lifetime of an address which never expires
+const INFINITY_LIFE_TIME: u32 = u32::MAX;
+
+/// Retrieves network interfaces by dumping links and addresses from the
+/// kernel
+pub(crate) fn show() -> Result<Vec<NetworkInterface>> {
+    query(&InterfaceQuery::new())
+}
+
+/// Retrieves the network interfaces matching the provided query, addresses
+/// are only dumped for the family of the query if any
+pub(crate) fn query(query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+    let mut socket = NetlinkSocket::open()?;
+    let mut network_interfaces: BTreeMap<u32, NetworkInterface> = BTreeMap::new();
+
+    for message in socket.dump(RTM_GETLINK, &[0; IFINFOMSG_LEN])? {
+        if message.kind != RTM_NEWLINK {
+            continue;
+        }
+
+        if let Some(network_interface) = parse_link(&message)? {
+            if query.matches_link(&network_interface.name, network_interface.flags) {
+                network_interfaces.insert(network_interface.index, network_interface);
+            }
+        }
+    }
+
+    if network_interfaces.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut ifaddrmsg = [0; IFADDRMSG_LEN];
+
+    ifaddrmsg[0] = match query.address_family() {
+        Some(Family::V4) => AF_INET as u8,
+        Some(Family::V6) => AF_INET6 as u8,
+        None => 0,
+    };
+
+    for (index, addr) in dump_addresses(&mut socket, &ifaddrmsg)? {
+        if let Some(network_interface) = network_interfaces.get_mut(&index) {
+            let addr = adjust_address(network_interface, addr);
+
+            network_interface.addr.push(addr);
+        }
+    }
+
+    let mut network_interfaces = network_interfaces.into_values().collect();
+
+    query.retain(&mut network_interfaces);
+
+    Ok(network_interfaces)
+}
+
+/// Retrieves the network interface with the provided index through a
+/// `RTM_GETLINK` request targeting it
+pub(crate) fn by_index(index: u32) -> Result<Option<NetworkInterface>> {
+    if !is_valid_index(index) {
+        return Ok(None);
+    }
+
+    lookup_link(&make_ifinfomsg(index))
+        .map_err(|error| error.with_interface(InterfaceId::Index(index)))
+}
+
+/// Whether an interface could have the provided index, the kernel rejects
+/// requests for the others as invalid instead of reporting a missing link
+pub(super) fn is_valid_index(index: u32) -> bool {
+    // indices are stored as a positive `int`
+    index != 0 && index <= i32::MAX as u32
+}
+
+/// Retrieves the network interface with the provided name through a
+/// `RTM_GETLINK` request targeting it
+pub(crate) fn by_name(name: &str) -> Result<Option<NetworkInterface>> {
+    // the kernel rejects names which could not belong to an interface
+    if name.is_empty() || name.len() >= IFNAMSIZ || name.contains('\0') {
+        return Ok(None);
+    }
+
+    let mut payload = make_ifinfomsg(0).to_vec();
+    let mut ifname = name.as_bytes().to_vec();
+
+    ifname.push(0);
+    payload.extend(encode_attribute(IFLA_IFNAME, &ifname));
+
+    lookup_link(&payload).map_err(|error| error.with_interface(InterfaceId::Name(name.to_string())))
+}
+
+/// Retrieves the network interface with the provided hardware address by
+/// dumping links only
+pub(crate) fn by_mac(mac_addr: &MacAddr) -> Result<Option<NetworkInterface>> {
+    let mut socket = NetlinkSocket::open()?;
+
+    // replies are filtered by `dump_addresses` on kernels lacking the option
+    socket.enable_strict_checking().ok();
+
+    for message in socket.dump(RTM_GETLINK, &[0; IFINFOMSG_LEN])? {
+        if message.kind != RTM_NEWLINK {
+            continue;
+        }
+
+        if let Some(mut network_interface) = parse_link(&message)? {
+            if network_interface.mac_addr.as_ref() == Some(mac_addr) {
+                push_addresses(&mut socket, &mut network_interface)?;
+
+                return Ok(Some(network_interface));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Retrieves the network interface holding the provided address by dumping
+/// the addresses of its family only
+pub(crate) fn by_ip(ip: IpAddr) -> Result<Option<NetworkInterface>> {
+    let mut socket = NetlinkSocket::open()?;
+    let mut ifaddrmsg = [0; IFADDRMSG_LEN];
+
+    ifaddrmsg[0] = match ip {
+        IpAddr::V4(_) => AF_INET as u8,
+        IpAddr::V6(_) => AF_INET6 as u8,
+    };
+
+    let index = dump_addresses(&mut socket, &ifaddrmsg)?
+        .into_iter()
+        .find(|(_, addr)| addr.ip() == ip)
+        .map(|(index, _)| index);
+
+    match index {
+        Some(index) => by_index(index),
+        None => Ok(None),
+    }
+}
+
+/// Sends a `RTM_GETLINK` request for a single link and retrieves its
+/// addresses, a missing link is reported by the kernel as `ENODEV`
+fn lookup_link(payload: &[u8]) -> Result<Option<NetworkInterface>> {
+    let mut socket = NetlinkSocket::open()?;
+
+    // replies are filtered by `dump_addresses` on kernels lacking the option
+    socket.enable_strict_checking().ok();
+
+    let messages = match socket.request(RTM_GETLINK, NLM_F_REQUEST, payload) {
+        Ok(messages) => messages,
+        Err(error) if error.raw_os_error() == Some(ENODEV) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    for message in messages {
+        if message.kind != RTM_NEWLINK {
+            continue;
+        }
+
+        if let Some(mut network_interface) = parse_link(&message)? {
+            push_addresses(&mut socket, &mut network_interface)?;
+
+            return Ok(Some(network_interface));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Dumps the addresses matching the provided `struct ifaddrmsg` alongside
+/// the index of the interface each one belongs to.
+///
+/// The family and interface index of the request are checked on every reply,
+/// as the kernel only filters on the latter when strict checking is enabled
+/// on the socket.
+fn dump_addresses(socket: &mut NetlinkSocket, ifaddrmsg: &[u8]) -> Result<Vec<(u32, Addr)>> {
+    let family = i32::from(ifaddrmsg[0]);
+    let index = read_u32(ifaddrmsg, 4).unwrap_or_default();
+
+    Ok(socket
+        .dump(RTM_GETADDR, ifaddrmsg)?
+        .iter()
+        .filter(|message| message.kind == RTM_NEWADDR)
+        .filter_map(parse_address)
+        .filter(|(addr_index, addr)| {
+            let addr_family = match addr {
+                Addr::V4(_) => AF_INET,
+                Addr::V6(_) => AF_INET6,
+            };
+
+            (family == 0 || family == addr_family) && (index == 0 || index == *addr_index)
+        })
+        .collect())
+}
+
+/// Appends the addresses of a network interface
+fn push_addresses(
+    socket: &mut NetlinkSocket,
+    network_interface: &mut NetworkInterface,
+) -> Result<()> {
+    let mut ifaddrmsg = [0; IFADDRMSG_LEN];
+
+    ifaddrmsg[4..8].copy_from_slice(&network_interface.index.to_ne_bytes());
+
+    for (_, addr) in dump_addresses(socket, &ifaddrmsg)? {
+        let addr = adjust_address(network_interface, addr);
+
+        network_interface.addr.push(addr);
+    }
+
+    Ok(())
+}
+
+/// Creates a `struct ifinfomsg` targeting the network interface with the
+/// provided index
+pub(super) fn make_ifinfomsg(index: u32) -> [u8; IFINFOMSG_LEN] {
+    let mut ifinfomsg = [0; IFINFOMSG_LEN];
+
+    ifinfomsg[4..8].copy_from_slice(&index.to_ne_bytes());
+    ifinfomsg
+}
+
+/// Adjusts an address to the network interface it belongs to, as
+/// point-to-point links have no broadcast domain even if a broadcast address
+/// was configured.
+///
+/// The kernel labels the IPv4 addresses without a label after the interface,
+/// such labels are dropped.
+pub(super) fn adjust_address(network_interface: &NetworkInterface, mut addr: Addr) -> Addr {
+    if network_interface.is_point_to_point() {
+        match &mut addr {
+            Addr::V4(ifaddr_v4) => ifaddr_v4.broadcast = None,
+            Addr::V6(ifaddr_v6) => ifaddr_v6.broadcast = None,
+        }
+    }
+
+    if let Addr::V4(ifaddr_v4) = &mut addr {
+        if ifaddr_v4.label.as_deref() == Some(network_interface.name.as_str()) {
+            ifaddr_v4.label = None;
+        }
+    }
+
+    addr
+}
+
+/// Builds a `NetworkInterface` without addresses from a `RTM_NEWLINK` or
+/// `RTM_DELLINK` message payload, which starts with a `struct ifinfomsg`.
+pub(super) fn parse_link(message: &Message) -> Result<Option<NetworkInterface>> {
+    let payload = &message.payload;
+
+    if payload.len() < IFINFOMSG_LEN {
+        return Ok(None);
+    }
+
+    // bridge ports are notified as `AF_BRIDGE` links on top of the
+    // `AF_UNSPEC` notifications of the interfaces themselves
+    if i32::from(payload[0]) == AF_BRIDGE {
+        return Ok(None);
+    }
+
+    let link_type = read_u16(payload, 2).unwrap_or_default();
+    let index = read_u32(payload, 4).unwrap_or_default();
+    let flags = read_u32(payload, 8).unwrap_or_default();
+    let mut os_name = None;
+    let mut mac_addr = None;
+    let mut link_kind = None;
+    // speed, duplex and the origin of the hardware address are only
+    // available on sysfs, see `Sysfs::enrich`
+    let mut link = LinkDetails {
+        link_type: Some(link_type),
+        ..LinkDetails::default()
+    };
+
+    for (kind, value) in Attributes::new(&payload[IFINFOMSG_LEN..]) {
+        match kind {
+            IFLA_IFNAME => os_name = Some(read_os_string(value)),
+            IFLA_ADDRESS => mac_addr = MacAddr::new(value),
+            IFLA_LINKINFO => link_kind = parse_link_info(value, link_type),
+            IFLA_MTU => link.mtu = read_u32(value, 0),
+            IFLA_OPERSTATE => link.operstate = value.first().map(|&value| OperState::from(value)),
+            IFLA_LINKMODE => link.link_mode = value.first().map(|&value| LinkMode::from(value)),
+            IFLA_CARRIER => link.carrier = value.first().map(|&value| value != 0),
+            IFLA_CARRIER_UP_COUNT => link.carrier_up_count = read_u32(value, 0),
+            IFLA_CARRIER_DOWN_COUNT => link.carrier_down_count = read_u32(value, 0),
+            _ => {}
+        }
+    }
+
+    let os_name = match os_name {
+        Some(os_name) => os_name,
+        None => return Ok(None),
+    };
+
+    let flags = make_interface_flags(flags);
+    // only the interfaces created by a virtual device driver have a kind
+    let kind = link_kind.unwrap_or(if flags.is_loopback() {
+        InterfaceKind::Loopback
+    } else {
+        InterfaceKind::Physical
+    });
+
+    Ok(Some(NetworkInterface {
+        name: os_name.to_string_lossy().into_owned(),
+        os_name,
+        addr: Vec::new(),
+        mac_addr,
+        index,
+        flags,
+        kind,
+        link,
+    }))
+}
+
+/// Builds an `InterfaceKind` from the nested attributes of `IFLA_LINKINFO`,
+/// holding the kind of virtual device alongside its kind specific data, and
+/// the `ARPHRD_*` link type of the interface
+fn parse_link_info(link_info: &[u8], link_type: u16) -> Option<InterfaceKind> {
+    let mut kind = None;
+    let mut data: &[u8] = &[];
+
+    for (attribute, value) in Attributes::new(link_info) {
+        match attribute {
+            // the kind is set by the driver, a malformed one should not
+            // prevent the link from being listed
+            IFLA_INFO_KIND => kind = Some(read_os_string(value).to_string_lossy().into_owned()),
+            IFLA_INFO_DATA => data = value,
+            _ => {}
+        }
+    }
+
+    let kind = kind?;
+    let kind = match kind.as_str() {
+        "bridge" => InterfaceKind::Bridge,
+        "vlan" => {
+            let mut id = 0;
+            let mut protocol = VlanProtocol::Ieee8021Q;
+
+            for (attribute, value) in Attributes::new(data) {
+                match attribute {
+                    IFLA_VLAN_ID => id = read_u16(value, 0).unwrap_or_default(),
+                    // in network byte order
+                    IFLA_VLAN_PROTOCOL => {
+                        if let Some(value) = read_u16(value, 0) {
+                            protocol = VlanProtocol::from(u16::from_be(value));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            InterfaceKind::Vlan { id, protocol }
+        }
+        "veth" => InterfaceKind::Veth,
+        "bond" => InterfaceKind::Bond,
+        // TAP devices carry Ethernet frames while TUN devices have no link
+        // layer, older kernels provide no data for either
+        "tun" if link_type == ARPHRD_ETHER => InterfaceKind::Tap,
+        "tun" => InterfaceKind::Tun,
+        "dummy" => InterfaceKind::Dummy,
+        "macvlan" => {
+            let mode = Attributes::new(data)
+                .find(|(attribute, _)| *attribute == IFLA_MACVLAN_MODE)
+                .and_then(|(_, value)| read_u32(value, 0))
+                .map(MacvlanMode::from)
+                .unwrap_or(MacvlanMode::Vepa);
+
+            InterfaceKind::Macvlan { mode }
+        }
+        "wireguard" => InterfaceKind::Wireguard,
+        _ => InterfaceKind::Other(kind),
+    };
+
+    Some(kind)
+}
+
+/// Builds an `Addr` from a `RTM_NEWADDR` or `RTM_DELADDR` message payload,
+/// which starts with a `struct ifaddrmsg`, alongside the index of the
+/// interface it belongs to.
+pub(super) fn parse_address(message: &Message) -> Option<(u32, Addr)> {
+    let payload = &message.payload;
+
+    if payload.len() < IFADDRMSG_LEN {
+        return None;
+    }
+
+    let family = i32::from(payload[0]);
+    let prefix_len = payload[1];
+    // superseded by `IFA_FLAGS` which is not truncated to 8 bits
+    let mut flags = u32::from(payload[2]);
+    let index = read_u32(payload, 4)?;
+    let mut address = None;
+    let mut local = None;
+    let mut broadcast = None;
+    let mut label = None;
+    let mut lifetimes = None;
+
+    for (kind, value) in Attributes::new(&payload[IFADDRMSG_LEN..]) {
+        match kind {
+            IFA_ADDRESS => address = Some(value),
+            IFA_LOCAL => local = Some(value),
+            IFA_BROADCAST => broadcast = Some(value),
+            IFA_LABEL => label = AddrLabel::from_bytes(read_os_string(value).as_bytes()),
+            IFA_FLAGS => flags = read_u32(value, 0).unwrap_or(flags),
+            IFA_CACHEINFO => lifetimes = parse_cache_info(value),
+            _ => {}
+        }
+    }
+
+    // `IFA_LOCAL` holds the local address on point-to-point links, where
+    // `IFA_ADDRESS` holds the one of the peer instead
+    let (ip, destination) = match (local, address) {
+        (Some(local), Some(address)) if local != address => (local, Some(address)),
+        (Some(local), _) => (local, None),
+        (None, address) => (address?, None),
+    };
+
+    let addr = match family {
+        AF_INET => Addr::V4(V4IfAddr {
+            ip: ipv4_from_bytes(ip)?,
+            broadcast: broadcast.and_then(ipv4_from_bytes),
+            destination: destination.and_then(ipv4_from_bytes),
+            netmask: Some(ipv4_netmask(prefix_len)),
+            label,
+        }),
+        AF_INET6 => {
+            let ip = ipv6_from_bytes(ip)?;
+            // `getifaddrs` reports the interface index as scope id for
+            // link-local addresses
+            let scope_id = match ipv6_scope(&ip) {
+                AddrScope::Link => index,
+                _ => 0,
+            };
+
+            Addr::V6(V6IfAddr {
+                ip,
+                broadcast: None,
+                destination: destination.and_then(ipv6_from_bytes),
+                netmask: Some(ipv6_netmask(prefix_len)),
+                scope_id,
+                flags: Some(AddrFlags::from_bits(flags)),
+                lifetimes,
+            })
+        }
+        _ => return None,
+    };
+
+    Some((index, addr))
+}
+
+/// Reads the lifetimes of an address from a `struct ifa_cacheinfo`, which
+/// starts with the preferred and valid lifetimes in seconds
+fn parse_cache_info(cache_info: &[u8]) -> Option<AddrLifetimes> {
+    let lifetime = |seconds| match seconds {
+        INFINITY_LIFE_TIME => None,
+        seconds => Some(Duration::from_secs(u64::from(seconds))),
+    };
+
+    Some(AddrLifetimes {
+        preferred: lifetime(read_u32(cache_info, 0)?),
+        valid: lifetime(read_u32(cache_info, 4)?),
+    })
+}
+
+fn ipv4_from_bytes(bytes: &[u8]) -> Option<Ipv4Addr> {
+    let octets: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+
+    Some(Ipv4Addr::from(octets))
+}
+
+fn ipv6_from_bytes(bytes: &[u8]) -> Option<Ipv6Addr> {
+    let octets: [u8; 16] = bytes.get(..16)?.try_into().ok()?;
+
+    Some(Ipv6Addr::from(octets))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use libc::{AF_INET, AF_INET6};
+
+    use super::{
+        dump_addresses, parse_address, parse_link_info, ARPHRD_ETHER, IFADDRMSG_LEN, IFA_ADDRESS,
+        IFA_CACHEINFO, IFA_FLAGS, IFA_LABEL, IFA_LOCAL, IFLA_INFO_DATA, IFLA_INFO_KIND,
+        IFLA_MACVLAN_MODE, IFLA_VLAN_ID, IFLA_VLAN_PROTOCOL, INFINITY_LIFE_TIME,
+    };
+    use crate::target::linux::netlink::{encode_attribute, Message, NetlinkSocket, RTM_NEWADDR};
+    use crate::{Addr, AddrFlags, InterfaceKind, MacvlanMode, VlanProtocol};
+
+    fn make_link_info(kind: &[u8], data: &[Vec<u8>]) -> Vec<u8> {
+        let mut link_info = encode_attribute(IFLA_INFO_KIND, kind);
+
+        link_info.extend(encode_attribute(IFLA_INFO_DATA | 1 << 15, &data.concat()));
+        link_info
+    }
+
+    #[test]
+    fn parses_link_info() {
+        let vlan = make_link_info(
+            b"vlan\0",
+            &[
+                encode_attribute(IFLA_VLAN_ID, &10u16.to_ne_bytes()),
+                encode_attribute(IFLA_VLAN_PROTOCOL, &0x88a8u16.to_be_bytes()),
+            ],
+        );
+        let macvlan = make_link_info(
+            b"macvlan\0",
+            &[encode_attribute(IFLA_MACVLAN_MODE, &4u32.to_ne_bytes())],
+        );
+
+        assert_eq!(
+            parse_link_info(&vlan, ARPHRD_ETHER),
+            Some(InterfaceKind::Vlan {
+                id: 10,
+                protocol: VlanProtocol::Ieee8021Ad,
+            })
+        );
+        assert_eq!(
+            parse_link_info(&macvlan, ARPHRD_ETHER),
+            Some(InterfaceKind::Macvlan {
+                mode: MacvlanMode::Bridge,
+            })
+        );
+        assert_eq!(
+            parse_link_info(&make_link_info(b"tun\0", &[]), ARPHRD_ETHER),
+            Some(InterfaceKind::Tap)
+        );
+        assert_eq!(
+            parse_link_info(&make_link_info(b"vxlan\0", &[]), ARPHRD_ETHER),
+            Some(InterfaceKind::Other(String::from("vxlan")))
+        );
+        assert_eq!(
+            parse_link_info(&make_link_info(b"vx\xff\0", &[]), ARPHRD_ETHER),
+            Some(InterfaceKind::Other(String::from("vx\u{fffd}")))
+        );
+        assert_eq!(parse_link_info(&[], ARPHRD_ETHER), None);
+    }
+
+    #[test]
+    fn parses_address_label() {
+        let mut payload = vec![AF_INET as u8, 24, 0, 0];
+
+        payload.extend(2u32.to_ne_bytes());
+        payload.extend(encode_attribute(IFA_LOCAL, &[192, 0, 2, 1]));
+        payload.extend(encode_attribute(IFA_LABEL, b"eth0:1\0"));
+
+        let message = Message {
+            kind: RTM_NEWADDR,
+            payload,
+        };
+
+        match parse_address(&message) {
+            Some((2, Addr::V4(ifaddr_v4))) => {
+                assert_eq!(
+                    ifaddr_v4.ip,
+                    "192.0.2.1".parse::<std::net::Ipv4Addr>().unwrap()
+                );
+                assert_eq!(ifaddr_v4.label.as_deref(), Some("eth0:1"));
+            }
+            addr => panic!("unexpected address {:?}", addr),
+        }
+
+        let mut payload = vec![AF_INET as u8, 24, 0, 0];
+
+        payload.extend(2u32.to_ne_bytes());
+        payload.extend(encode_attribute(IFA_LOCAL, &[192, 0, 2, 1]));
+        payload.extend(encode_attribute(IFA_LABEL, b"eth\xff:1\0"));
+
+        let message = Message {
+            kind: RTM_NEWADDR,
+            payload,
+        };
+
+        match parse_address(&message) {
+            Some((2, Addr::V4(ifaddr_v4))) => {
+                assert_eq!(ifaddr_v4.label.as_deref(), Some("eth\u{fffd}:1"));
+            }
+            addr => panic!("unexpected address {:?}", addr),
+        }
+    }
+
+    #[test]
+    fn parses_address_flags_and_lifetimes() {
+        // the 8 bits `ifa_flags` only hold `IFA_F_TEMPORARY`
+        let mut payload = vec![AF_INET6 as u8, 64, 0x01, 0];
+        let mut cache_info = 300u32.to_ne_bytes().to_vec();
+
+        cache_info.extend(INFINITY_LIFE_TIME.to_ne_bytes());
+        cache_info.extend([0; 8]);
+        payload.extend(2u32.to_ne_bytes());
+        payload.extend(encode_attribute(
+            IFA_ADDRESS,
+            &"2001:db8::1"
+                .parse::<std::net::Ipv6Addr>()
+                .unwrap()
+                .octets(),
+        ));
+        payload.extend(encode_attribute(IFA_FLAGS, &0x201u32.to_ne_bytes()));
+        payload.extend(encode_attribute(IFA_CACHEINFO, &cache_info));
+
+        let message = Message {
+            kind: RTM_NEWADDR,
+            payload,
+        };
+
+        match parse_address(&message) {
+            Some((2, Addr::V6(ifaddr_v6))) => {
+                let lifetimes = ifaddr_v6.lifetimes.unwrap();
+
+                assert_eq!(
+                    ifaddr_v6.flags,
+                    Some(AddrFlags::TEMPORARY | AddrFlags::NOPREFIXROUTE)
+                );
+                assert_eq!(lifetimes.preferred, Some(Duration::from_secs(300)));
+                assert_eq!(lifetimes.valid, None);
+            }
+            addr => panic!("unexpected address {:?}", addr),
+        }
+    }
+
+    #[test]
+    fn filters_address_dumps_without_strict_checking() {
+        // strict checking is left disabled so the kernel ignores the index
+        let mut socket = NetlinkSocket::open().unwrap();
+        let mut ifaddrmsg = [0; IFADDRMSG_LEN];
+
+        ifaddrmsg[0] = AF_INET as u8;
+        ifaddrmsg[4..8].copy_from_slice(&1u32.to_ne_bytes());
+
+        for (index, addr) in dump_addresses(&mut socket, &ifaddrmsg).unwrap() {
+            assert_eq!(index, 1);
+            assert!(matches!(addr, Addr::V4(_)), "{:?}", addr);
+        }
+    }
+}
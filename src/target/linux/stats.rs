@@ -0,0 +1,163 @@
+//! Traffic statistics retrieval from the `IFLA_STATS64` attribute of
+//! `RTM_NEWLINK` messages, falling back to the counters exposed on sysfs.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+//! <https://www.kernel.org/doc/html/latest/networking/statistics.html>
+use std::collections::BTreeMap;
+
+use libc::ENODEV;
+
+use crate::{InterfaceId, InterfaceStats, NetworkInterface, Result};
+
+use super::netlink::{
+    is_unavailable, read_u32, Attributes, Message, NetlinkSocket, NLM_F_REQUEST, RTM_GETLINK,
+    RTM_NEWLINK,
+};
+use super::rtnetlink::{is_valid_index, make_ifinfomsg, IFINFOMSG_LEN};
+use super::netns::in_process_netns;
+use super::sysfs::Sysfs;
+
+const IFLA_STATS64: u16 = 23;
+
+impl InterfaceStats {
+    /// Retrieves the traffic statistics of every network interface, keyed and
+    /// sorted by the interface index
+    pub fn show() -> Result<BTreeMap<u32, InterfaceStats>> {
+        match netlink_show() {
+            // same as `NetworkInterface::show`, fallback to sysfs if netlink
+            // sockets are not available
+            Err(error) if is_unavailable(&error) => sysfs_show(),
+            result => result,
+        }
+    }
+
+    /// Retrieves the traffic statistics of the network interface with the
+    /// provided index, or `None` if there is no such interface
+    pub fn by_index(index: u32) -> Result<Option<InterfaceStats>> {
+        match netlink_by_index(index) {
+            Err(error) if is_unavailable(&error) => Ok(sysfs_show()?.remove(&index)),
+            result => result,
+        }
+    }
+}
+
+impl NetworkInterface {
+    /// Retrieves the traffic statistics of this network interface, or `None`
+    /// if it no longer exists
+    pub fn stats(&self) -> Result<Option<InterfaceStats>> {
+        InterfaceStats::by_index(self.index)
+    }
+}
+
+fn netlink_show() -> Result<BTreeMap<u32, InterfaceStats>> {
+    let mut socket = NetlinkSocket::open()?;
+    let mut stats = BTreeMap::new();
+
+    for message in socket.dump(RTM_GETLINK, &[0; IFINFOMSG_LEN])? {
+        if let Some((index, interface_stats)) = parse_link_stats(&message) {
+            stats.insert(index, interface_stats);
+        }
+    }
+
+    Ok(stats)
+}
+
+fn netlink_by_index(index: u32) -> Result<Option<InterfaceStats>> {
+    if !is_valid_index(index) {
+        return Ok(None);
+    }
+
+    let mut socket = NetlinkSocket::open()?;
+    let messages = match socket.request(RTM_GETLINK, NLM_F_REQUEST, &make_ifinfomsg(index)) {
+        Ok(messages) => messages,
+        Err(error) if error.raw_os_error() == Some(ENODEV) => return Ok(None),
+        Err(error) => return Err(error.with_interface(InterfaceId::Index(index))),
+    };
+
+    Ok(messages
+        .iter()
+        .filter_map(parse_link_stats)
+        .find(|(link_index, _)| *link_index == index)
+        .map(|(_, interface_stats)| interface_stats))
+}
+
+/// Retrieves the interface index alongside the `struct rtnl_link_stats64`
+/// carried by a `RTM_NEWLINK` message
+fn parse_link_stats(message: &Message) -> Option<(u32, InterfaceStats)> {
+    if message.kind != RTM_NEWLINK || message.payload.len() < IFINFOMSG_LEN {
+        return None;
+    }
+
+    let index = read_u32(&message.payload, 4)?;
+    let (_, value) = Attributes::new(&message.payload[IFINFOMSG_LEN..])
+        .find(|(kind, _)| *kind == IFLA_STATS64)?;
+    let counter = |position: usize| {
+        let bytes = value.get(position * 8..position * 8 + 8)?;
+        let mut octets = [0; 8];
+
+        octets.copy_from_slice(bytes);
+
+        Some(u64::from_ne_bytes(octets))
+    };
+
+    Some((
+        index,
+        InterfaceStats {
+            rx_packets: counter(0)?,
+            tx_packets: counter(1)?,
+            rx_bytes: counter(2)?,
+            tx_bytes: counter(3)?,
+            rx_errors: counter(4)?,
+            tx_errors: counter(5)?,
+            rx_dropped: counter(6)?,
+            tx_dropped: counter(7)?,
+            multicast: counter(8)?,
+            collisions: counter(9)?,
+        },
+    ))
+}
+
+/// Retrieves the traffic statistics from the `statistics` directory of each
+/// network interface on sysfs.
+///
+/// Nothing is retrieved from threads moved to another network namespace, as
+/// sysfs lists the interfaces of the namespace of the process, which could
+/// have the same indices.
+fn sysfs_show() -> Result<BTreeMap<u32, InterfaceStats>> {
+    if !in_process_netns() {
+        return Ok(BTreeMap::new());
+    }
+
+    Sysfs::new().read_statistics()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{netlink_show, sysfs_show};
+    use crate::test::in_network_namespace;
+    use crate::InterfaceStats;
+
+    #[test]
+    fn netlink_and_sysfs_stats_match() {
+        let netlink = netlink_show().unwrap();
+        let sysfs = sysfs_show().unwrap();
+
+        assert!(netlink.keys().eq(sysfs.keys()));
+        assert_eq!(InterfaceStats::by_index(u32::MAX).unwrap(), None);
+
+        for index in netlink.keys() {
+            assert!(InterfaceStats::by_index(*index).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn skips_sysfs_stats_in_another_netns() {
+        assert!(!sysfs_show().unwrap().is_empty());
+
+        if let Some(stats) = in_network_namespace(sysfs_show) {
+            assert_eq!(stats.unwrap(), Default::default());
+        }
+    }
+}
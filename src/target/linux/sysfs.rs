@@ -0,0 +1,598 @@
+//! Network interfaces attributes exposed on sysfs under `class/net/<name>`,
+//! used to complete the details not provided by the other backends.
+//!
+//! ## References
+//!
+//! <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net>
+//! <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net-statistics>
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use super::netns::in_process_netns;
+use crate::{
+    AddrAssignType, Duplex, Error, InterfaceFlags, InterfaceKind, InterfaceStats, LinkDetails,
+    LinkMode, NetworkInterface, OperState, Result, VlanProtocol,
+};
+
+const SYSFS_ROOT: &str = "/sys";
+const PROC_NET_VLAN_CONFIG: &str = "net/vlan/config";
+
+const IFF_TAP: u32 = 0x0002;
+
+/// Reader of the network interfaces attributes exposed on sysfs.
+///
+/// The sysfs mount point defaults to `/sys` and can be changed in order to
+/// read a copy of the tree, e.g. from fixtures, where symbolic links could be
+/// stored as plain files holding their target. The procfs is looked up next to
+/// the sysfs mount point, i.e. `/proc` for `/sys`.
+///
+/// Interfaces are only read when their `ifindex` attribute matches the index
+/// they were retrieved with, and the tree mounted on `/sys` is not read from
+/// threads moved to another network namespace, as it still lists the
+/// interfaces of the namespace of the process.
+///
+/// ```no_run
+/// use network_interface::{NetworkInterface, NetworkInterfaceConfig, Sysfs};
+///
+/// let mut network_interfaces = NetworkInterface::show().unwrap();
+///
+/// Sysfs::new().enrich_all(&mut network_interfaces);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sysfs {
+    root: PathBuf,
+}
+
+impl Default for Sysfs {
+    fn default() -> Self {
+        Sysfs::with_root(SYSFS_ROOT)
+    }
+}
+
+impl Sysfs {
+    /// Creates a reader of the sysfs tree mounted on `/sys`
+    pub fn new() -> Self {
+        Sysfs::default()
+    }
+
+    /// Creates a reader of the sysfs tree mounted on the provided path
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        Sysfs { root: root.into() }
+    }
+
+    /// Retrieves the sysfs mount point
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Reads the link layer details of the network interface with the
+    /// provided name and index, or `None` if there is no such interface.
+    ///
+    /// Attributes which cannot be read, such as the speed of a virtual
+    /// device, are left as `None`.
+    pub fn read_link<N: AsRef<OsStr>>(&self, name: N, index: u32) -> Option<LinkDetails> {
+        let path = self.link_path(name.as_ref(), index)?;
+
+        Some(read_link_details(&path))
+    }
+
+    /// Completes a network interface with the details read from sysfs,
+    /// filling its link layer details as well as its hardware address and kind
+    /// when unknown.
+    ///
+    /// The link layer details already reported, such as the ones retrieved
+    /// through netlink, are kept.
+    ///
+    /// Returns `false` if there is no such interface on sysfs.
+    pub fn enrich(&self, network_interface: &mut NetworkInterface) -> bool {
+        // names which are not valid UTF-8 are only kept as is on `os_name`
+        let name = &network_interface.os_name;
+        let path = match self.link_path(name, network_interface.index) {
+            Some(path) => path,
+            None => return false,
+        };
+        let link = read_link_details(&path);
+
+        if network_interface.mac_addr.is_none() {
+            network_interface.mac_addr =
+                read_attribute(&path.join("address")).and_then(|value| value.parse().ok());
+        }
+
+        if network_interface.kind == InterfaceKind::Unknown {
+            network_interface.kind =
+                self.read_kind(name, network_interface.index, network_interface.flags);
+        }
+
+        let old = &mut network_interface.link;
+
+        network_interface.link = LinkDetails {
+            link_type: old.link_type.or(link.link_type),
+            mtu: old.mtu.or(link.mtu),
+            operstate: old.operstate.or(link.operstate),
+            carrier: old.carrier.or(link.carrier),
+            carrier_up_count: old.carrier_up_count.or(link.carrier_up_count),
+            carrier_down_count: old.carrier_down_count.or(link.carrier_down_count),
+            link_mode: old.link_mode.or(link.link_mode),
+            speed: old.speed.or(link.speed),
+            duplex: old.duplex.or(link.duplex),
+            addr_assign_type: old.addr_assign_type.or(link.addr_assign_type),
+            device: old.device.take().or(link.device),
+        };
+
+        true
+    }
+
+    /// Completes every provided network interface, see `Sysfs::enrich`
+    pub fn enrich_all(&self, network_interfaces: &mut [NetworkInterface]) {
+        for network_interface in network_interfaces {
+            self.enrich(network_interface);
+        }
+    }
+
+    /// Retrieves the kind of the network interface with the provided name,
+    /// index and flags.
+    ///
+    /// Virtual devices without a sysfs footprint, such as veths, dummies or
+    /// MACVLANs, cannot be told apart and are reported as
+    /// `InterfaceKind::Unknown`.
+    pub(super) fn read_kind(
+        &self,
+        name: &OsStr,
+        index: u32,
+        flags: InterfaceFlags,
+    ) -> InterfaceKind {
+        if flags.is_loopback() {
+            return InterfaceKind::Loopback;
+        }
+
+        let path = match self.link_path(name, index) {
+            Some(path) => path,
+            None => return InterfaceKind::Unknown,
+        };
+
+        if let Some(tun_flags) = read_attribute(&path.join("tun_flags")) {
+            return match u32::from_str_radix(tun_flags.trim_start_matches("0x"), 16) {
+                Ok(tun_flags) if tun_flags & IFF_TAP != 0 => InterfaceKind::Tap,
+                _ => InterfaceKind::Tun,
+            };
+        }
+
+        let uevent = fs::read_to_string(path.join("uevent")).unwrap_or_default();
+
+        if let Some(kind) = self.make_kind(name, &uevent) {
+            return kind;
+        }
+
+        if path.join("bridge").is_dir() {
+            InterfaceKind::Bridge
+        } else if path.join("bonding").is_dir() {
+            InterfaceKind::Bond
+        } else if read_device(&path).is_some() {
+            InterfaceKind::Physical
+        } else {
+            InterfaceKind::Unknown
+        }
+    }
+
+    /// Retrieves the traffic statistics from the `statistics` directory of
+    /// each network interface, keyed by the interface index
+    pub(super) fn read_statistics(&self) -> Result<BTreeMap<u32, InterfaceStats>> {
+        let net = self.root.join("class").join("net");
+        let make_error = |error| Error::ReadFileError {
+            path: net.display().to_string(),
+            source: error,
+        };
+        let mut stats = BTreeMap::new();
+
+        for entry in fs::read_dir(&net).map_err(make_error)? {
+            let path = entry.map_err(make_error)?.path();
+
+            // interfaces could be removed while iterating
+            let index =
+                match read_attribute(&path.join("ifindex")).and_then(|value| value.parse().ok()) {
+                    Some(index) => index,
+                    None => continue,
+                };
+
+            if let Some(interface_stats) = read_counters(&path.join("statistics")) {
+                stats.insert(index, interface_stats);
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Retrieves the directory of the network interface with the provided name
+    /// and index, if listed on this sysfs tree.
+    ///
+    /// Another interface could hold the name, e.g. when it was renamed since
+    /// it was retrieved or when this tree lists the interfaces of another
+    /// network namespace.
+    fn link_path(&self, name: &OsStr, index: u32) -> Option<PathBuf> {
+        if self.root == Path::new(SYSFS_ROOT) && !in_process_netns() {
+            return None;
+        }
+
+        let path = self.interface_path(name)?;
+        let ifindex = read_attribute(&path.join("ifindex"))?.parse::<u32>().ok()?;
+
+        if ifindex != index {
+            return None;
+        }
+
+        Some(path)
+    }
+
+    /// Retrieves the directory of a network interface, rejecting names which
+    /// would escape `class/net`
+    fn interface_path(&self, name: &OsStr) -> Option<PathBuf> {
+        let bytes = name.as_bytes();
+
+        if bytes.is_empty() || bytes == b"." || bytes == b".." || bytes.contains(&b'/') {
+            return None;
+        }
+
+        Some(self.root.join("class").join("net").join(name))
+    }
+
+    /// Builds an `InterfaceKind` from the `DEVTYPE` of an interface `uevent`
+    /// file, if any
+    fn make_kind(&self, name: &OsStr, uevent: &str) -> Option<InterfaceKind> {
+        let devtype = uevent
+            .lines()
+            .find_map(|line| line.strip_prefix("DEVTYPE="))?;
+        let kind = match devtype {
+            "bridge" => InterfaceKind::Bridge,
+            "bond" => InterfaceKind::Bond,
+            // the tag protocol is not exposed, only 802.1Q VLANs are listed
+            "vlan" => match self.read_vlan_id(name) {
+                Some(id) => InterfaceKind::Vlan {
+                    id,
+                    protocol: VlanProtocol::Ieee8021Q,
+                },
+                None => InterfaceKind::Other(String::from(devtype)),
+            },
+            "wireguard" => InterfaceKind::Wireguard,
+            "wlan" | "wwan" => InterfaceKind::Physical,
+            devtype => InterfaceKind::Other(String::from(devtype)),
+        };
+
+        Some(kind)
+    }
+
+    /// Retrieves the identifier of a VLAN from `net/vlan/config` on the procfs
+    /// next to the sysfs mount point
+    fn read_vlan_id(&self, name: &OsStr) -> Option<u16> {
+        let path = self.root.with_file_name("proc").join(PROC_NET_VLAN_CONFIG);
+        let config = fs::read_to_string(path).ok()?;
+
+        parse_vlan_id(&config, &name.to_string_lossy())
+    }
+}
+
+/// Reads a sysfs attribute, trimming its trailing newline
+fn read_attribute(path: &Path) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?;
+
+    Some(String::from(value.trim()))
+}
+
+/// Reads the link layer details from the sysfs directory of a network
+/// interface
+fn read_link_details(path: &Path) -> LinkDetails {
+    let attribute = |name: &str| read_attribute(&path.join(name));
+
+    LinkDetails {
+        link_type: attribute("type").and_then(|value| value.parse().ok()),
+        mtu: attribute("mtu").and_then(|value| value.parse().ok()),
+        operstate: attribute("operstate").map(|value| make_operstate(&value)),
+        carrier: attribute("carrier").map(|value| value == "1"),
+        carrier_up_count: attribute("carrier_up_count").and_then(|value| value.parse().ok()),
+        carrier_down_count: attribute("carrier_down_count").and_then(|value| value.parse().ok()),
+        link_mode: attribute("link_mode")
+            .and_then(|value| value.parse::<u8>().ok())
+            .map(LinkMode::from),
+        // `-1` is reported when the speed is unknown
+        speed: attribute("speed").and_then(|value| value.parse().ok()),
+        duplex: attribute("duplex").and_then(|value| make_duplex(&value)),
+        addr_assign_type: attribute("addr_assign_type")
+            .and_then(|value| value.parse::<u8>().ok())
+            .map(AddrAssignType::from),
+        device: read_device(path),
+    }
+}
+
+/// Reads the counters from the `statistics` directory of a network interface
+fn read_counters(statistics: &Path) -> Option<InterfaceStats> {
+    let counter = |name: &str| read_attribute(&statistics.join(name))?.parse().ok();
+
+    Some(InterfaceStats {
+        rx_bytes: counter("rx_bytes")?,
+        tx_bytes: counter("tx_bytes")?,
+        rx_packets: counter("rx_packets")?,
+        tx_packets: counter("tx_packets")?,
+        rx_errors: counter("rx_errors")?,
+        tx_errors: counter("tx_errors")?,
+        rx_dropped: counter("rx_dropped")?,
+        tx_dropped: counter("tx_dropped")?,
+        multicast: counter("multicast")?,
+        collisions: counter("collisions")?,
+    })
+}
+
+/// Retrieves the name of the bus device an interface `device` symbolic link
+/// points to, if any.
+///
+/// Copies of the tree unable to hold symbolic links, such as fixtures, can
+/// store the link target as the content of a plain `device` file instead.
+fn read_device(path: &Path) -> Option<String> {
+    let device = path.join("device");
+    let target = match fs::read_link(&device) {
+        Ok(target) => target,
+        Err(_) => PathBuf::from(read_attribute(&device)?),
+    };
+
+    Some(target.file_name()?.to_string_lossy().into_owned())
+}
+
+fn make_operstate(operstate: &str) -> OperState {
+    match operstate {
+        "notpresent" => OperState::NotPresent,
+        "down" => OperState::Down,
+        "lowerlayerdown" => OperState::LowerLayerDown,
+        "testing" => OperState::Testing,
+        "dormant" => OperState::Dormant,
+        "up" => OperState::Up,
+        _ => OperState::Unknown,
+    }
+}
+
+fn make_duplex(duplex: &str) -> Option<Duplex> {
+    match duplex {
+        "half" => Some(Duplex::Half),
+        "full" => Some(Duplex::Full),
+        _ => None,
+    }
+}
+
+/// Parses the identifier of a VLAN from the content of
+/// `/proc/net/vlan/config`, which lists a `name | id | device` line for each
+/// VLAN after two header lines
+fn parse_vlan_id(config: &str, name: &str) -> Option<u16> {
+    config.lines().skip(2).find_map(|line| {
+        let mut fields = line.split('|').map(str::trim);
+
+        if fields.next()? != name {
+            return None;
+        }
+
+        fields.next()?.parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{OsStr, OsString};
+
+    use super::{parse_vlan_id, Sysfs};
+    use crate::test::in_network_namespace;
+    use crate::{
+        AddrAssignType, Duplex, InterfaceFlags, InterfaceKind, LinkDetails, LinkMode,
+        NetworkInterface, NetworkInterfaceConfig, OperState, VlanProtocol,
+    };
+
+    fn fixtures() -> Sysfs {
+        Sysfs::with_root(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sysfs"))
+    }
+
+    #[test]
+    fn reads_link_details() {
+        let sysfs = fixtures();
+
+        assert_eq!(
+            sysfs.read_link("eth0", 2),
+            Some(LinkDetails {
+                link_type: Some(1),
+                mtu: Some(1500),
+                operstate: Some(OperState::Up),
+                carrier: Some(true),
+                carrier_up_count: Some(3),
+                carrier_down_count: Some(2),
+                link_mode: Some(LinkMode::Default),
+                speed: Some(1000),
+                duplex: Some(Duplex::Full),
+                addr_assign_type: Some(AddrAssignType::Permanent),
+                device: Some(String::from("0000:00:03.0")),
+            })
+        );
+        assert_eq!(
+            sysfs.read_link("eth1", 3),
+            Some(LinkDetails {
+                link_type: Some(1),
+                mtu: Some(9000),
+                operstate: Some(OperState::Down),
+                carrier: None,
+                carrier_up_count: None,
+                carrier_down_count: None,
+                link_mode: None,
+                speed: None,
+                duplex: None,
+                addr_assign_type: Some(AddrAssignType::Permanent),
+                device: Some(String::from("0000:00:04.0")),
+            })
+        );
+        assert_eq!(
+            sysfs.read_link("lo", 1).unwrap().operstate,
+            Some(OperState::Unknown)
+        );
+        assert_eq!(
+            sysfs.read_link("wlan0", 4).unwrap().operstate,
+            Some(OperState::Dormant)
+        );
+        assert_eq!(sysfs.read_link("missing0", 9), None);
+        assert_eq!(sysfs.read_link("..", 0), None);
+    }
+
+    #[test]
+    fn reads_interface_kinds() {
+        let sysfs = fixtures();
+        let kind = |name: &str, index| sysfs.read_kind(OsStr::new(name), index, InterfaceFlags::UP);
+
+        assert_eq!(kind("eth0", 2), InterfaceKind::Physical);
+        assert_eq!(kind("wlan0", 4), InterfaceKind::Physical);
+        assert_eq!(kind("br0", 5), InterfaceKind::Bridge);
+        assert_eq!(kind("tap0", 6), InterfaceKind::Tap);
+        assert_eq!(kind("veth0", 7), InterfaceKind::Unknown);
+        assert_eq!(kind("br0", 2), InterfaceKind::Unknown);
+        assert_eq!(
+            kind("eth0.10", 8),
+            InterfaceKind::Vlan {
+                id: 10,
+                protocol: VlanProtocol::Ieee8021Q,
+            }
+        );
+        assert_eq!(
+            sysfs.read_kind(OsStr::new("lo"), 1, InterfaceFlags::LOOPBACK),
+            InterfaceKind::Loopback
+        );
+    }
+
+    #[test]
+    fn enriches_network_interfaces() {
+        let mut network_interface =
+            NetworkInterface::new_afinet("tap0", "192.0.2.1".parse().unwrap(), None, None, 6);
+
+        assert!(fixtures().enrich(&mut network_interface));
+        assert_eq!(network_interface.kind, InterfaceKind::Tap);
+        assert_eq!(
+            network_interface.mac_addr,
+            Some("02:fc:00:00:00:06".parse().unwrap())
+        );
+        assert_eq!(
+            network_interface.link.addr_assign_type,
+            Some(AddrAssignType::Random)
+        );
+        assert_eq!(network_interface.link.device, None);
+
+        let mut network_interface =
+            NetworkInterface::new_afinet("eth0", "192.0.2.1".parse().unwrap(), None, None, 2);
+
+        network_interface.link.operstate = Some(OperState::LowerLayerDown);
+
+        assert!(fixtures().enrich(&mut network_interface));
+        assert_eq!(
+            network_interface.link.operstate,
+            Some(OperState::LowerLayerDown)
+        );
+        assert_eq!(network_interface.link.speed, Some(1000));
+
+        // the lossy name differs from the one on sysfs for non UTF-8 names
+        let mut network_interface = NetworkInterface::new_afinet(
+            "eth\u{fffd}",
+            "192.0.2.1".parse().unwrap(),
+            None,
+            None,
+            3,
+        );
+
+        network_interface.os_name = OsString::from("eth1");
+
+        assert!(fixtures().enrich(&mut network_interface));
+        assert_eq!(network_interface.link.mtu, Some(9000));
+
+        let mut network_interface =
+            NetworkInterface::new_afinet("missing0", "192.0.2.1".parse().unwrap(), None, None, 9);
+
+        assert!(!fixtures().enrich(&mut network_interface));
+        assert_eq!(network_interface.link, LinkDetails::default());
+    }
+
+    #[test]
+    fn skips_interfaces_with_another_index() {
+        // `eth0` has the index 2 on the fixtures, e.g. the name could have been
+        // taken by another interface since the listing
+        let mut network_interface =
+            NetworkInterface::new_afinet("eth0", "192.0.2.1".parse().unwrap(), None, None, 9);
+
+        assert!(!fixtures().enrich(&mut network_interface));
+        assert_eq!(network_interface.mac_addr, None);
+        assert_eq!(network_interface.kind, InterfaceKind::Unknown);
+        assert_eq!(network_interface.link, LinkDetails::default());
+        assert_eq!(fixtures().read_link("eth0", 9), None);
+    }
+
+    #[test]
+    fn skips_interfaces_of_another_netns() {
+        let loopback = || {
+            NetworkInterface::show()
+                .unwrap()
+                .into_iter()
+                .find(|network_interface| network_interface.is_loopback())
+                .unwrap()
+        };
+
+        assert!(Sysfs::new().enrich(&mut loopback()));
+
+        // `lo` has the same name and index in every namespace, while `/sys`
+        // still lists the one of the process
+        let enriched = in_network_namespace(move || {
+            let mut network_interface = loopback();
+            let link = network_interface.link.clone();
+
+            (
+                Sysfs::new().enrich(&mut network_interface),
+                network_interface.link == link,
+            )
+        });
+
+        if let Some(enriched) = enriched {
+            assert_eq!(enriched, (false, true));
+        }
+    }
+
+    #[test]
+    fn reads_statistics() {
+        let stats = fixtures().read_statistics().unwrap();
+
+        assert_eq!(stats.keys().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(stats[&2].rx_bytes, 1234);
+        assert_eq!(stats[&2].tx_packets, 56);
+        assert_eq!(stats[&3].rx_bytes, 0);
+    }
+
+    #[test]
+    fn parses_uevent_devtype() {
+        let make_kind = |name: &str, uevent| fixtures().make_kind(OsStr::new(name), uevent);
+
+        assert_eq!(
+            make_kind("br0", "DEVTYPE=bridge\nINTERFACE=br0\nIFINDEX=3\n"),
+            Some(InterfaceKind::Bridge)
+        );
+        assert_eq!(
+            make_kind("wlan0", "DEVTYPE=wlan\nINTERFACE=wlan0\nIFINDEX=4\n"),
+            Some(InterfaceKind::Physical)
+        );
+        assert_eq!(
+            make_kind("vx0", "DEVTYPE=vxlan\nINTERFACE=vx0\nIFINDEX=5\n"),
+            Some(InterfaceKind::Other(String::from("vxlan")))
+        );
+        assert_eq!(make_kind("eth0", "INTERFACE=eth0\nIFINDEX=2\n"), None);
+    }
+
+    #[test]
+    fn parses_proc_net_vlan_config() {
+        let config = "\
+VLAN Dev name    | VLAN ID
+Name-Type: VLAN_NAME_TYPE_RAW_PLUS_VID_NO_PAD
+eth0.10        | 10  | eth0
+eth0.200       | 200  | eth0
+";
+
+        assert_eq!(parse_vlan_id(config, "eth0.10"), Some(10));
+        assert_eq!(parse_vlan_id(config, "eth0.200"), Some(200));
+        assert_eq!(parse_vlan_id(config, "eth0.30"), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Network interface changes notified by the kernel on the `RTMGRP_LINK`,
+//! `RTMGRP_IPV4_IFADDR` and `RTMGRP_IPV6_IFADDR` multicast groups of a
+//! `NETLINK_ROUTE` socket.
+//!
+//! ## References
+//!
+//! <https://man7.org/linux/man-pages/man7/rtnetlink.7.html>
+use std::collections::{BTreeMap, VecDeque};
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use libc::ENOBUFS;
+
+use crate::change::diff_link;
+use crate::{diff, Addr, InterfaceChange, NetworkInterface, Result};
+
+use super::netlink::{
+    Message, NetlinkSocket, RTMGRP_IPV4_IFADDR, RTMGRP_IPV6_IFADDR, RTMGRP_LINK, RTM_DELADDR,
+    RTM_DELLINK, RTM_NEWADDR, RTM_NEWLINK,
+};
+use super::rtnetlink::{self, adjust_address, parse_address, parse_link};
+
+/// Watches the system's network interfaces for changes.
+///
+/// The watcher keeps track of the network interfaces configuration, starting
+/// from a snapshot taken when it is created, in order to turn the kernel
+/// notifications into `InterfaceChange`s.
+///
+/// Iterating over a `Watcher` blocks until the next change is available.
+/// Alternatively, the raw file descriptor of the underlying netlink socket
+/// can be registered on an existing event loop, calling `Watcher::drain` once
+/// it becomes readable.
+pub struct Watcher {
+    socket: NetlinkSocket,
+    network_interfaces: BTreeMap<u32, NetworkInterface>,
+    changes: VecDeque<InterfaceChange>,
+}
+
+impl NetworkInterface {
+    /// Watches the system's network interfaces for changes.
+    ///
+    /// Interfaces being added, removed, renamed or changing hardware address
+    /// or flags, as well as addresses being added or removed, are reported by
+    /// the returned `Watcher`.
+    pub fn watch() -> Result<Watcher> {
+        // subscribe before taking the snapshot so no change is missed, the
+        // notifications already reflected on the snapshot yield no change
+        let socket =
+            NetlinkSocket::open_with_groups(RTMGRP_LINK | RTMGRP_IPV4_IFADDR | RTMGRP_IPV6_IFADDR)?;
+        let network_interfaces = rtnetlink::show()?
+            .into_iter()
+            .map(|network_interface| (network_interface.index, network_interface))
+            .collect();
+
+        Ok(Watcher {
+            socket,
+            network_interfaces,
+            changes: VecDeque::new(),
+        })
+    }
+}
+
+impl Watcher {
+    /// Retrieves the network interfaces as known by the watcher after the
+    /// changes reported so far, sorted by index
+    pub fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        self.network_interfaces.values().cloned().collect()
+    }
+
+    /// Waits for the next change on the system's network interfaces.
+    ///
+    /// If the watcher is in non-blocking mode, `Ok(None)` is returned when no
+    /// change is available.
+    pub fn next_change(&mut self) -> Result<Option<InterfaceChange>> {
+        while self.changes.is_empty() {
+            if !self.receive(false)? {
+                return Ok(None);
+            }
+        }
+
+        Ok(self.changes.pop_front())
+    }
+
+    /// Retrieves every change available without blocking, as expected once
+    /// the watcher file descriptor is reported readable by an event loop
+    pub fn drain(&mut self) -> Result<Vec<InterfaceChange>> {
+        while self.receive(true)? {}
+
+        Ok(self.changes.drain(..).collect())
+    }
+
+    /// Moves the watcher into or out of non-blocking mode
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.socket.set_nonblocking(nonblocking)
+    }
+
+    /// Receives a single datagram of notifications, returning `false` if no
+    /// datagram was available
+    fn receive(&mut self, nonblocking: bool) -> Result<bool> {
+        let messages = match self.socket.recv_multicast(nonblocking) {
+            Ok(Some(messages)) => messages,
+            Ok(None) => return Ok(false),
+            // notifications were dropped as the socket buffer overflowed
+            Err(error) if error.raw_os_error() == Some(ENOBUFS) => {
+                self.resync()?;
+                return Ok(true);
+            }
+            Err(error) => return Err(error),
+        };
+
+        for message in messages {
+            self.apply(&message)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Takes a new snapshot of the network interfaces and reports the changes
+    /// from the last known state
+    fn resync(&mut self) -> Result<()> {
+        let old = self.network_interfaces();
+        let new = rtnetlink::show()?;
+
+        self.changes.extend(diff(&old, &new));
+        self.network_interfaces = new
+            .into_iter()
+            .map(|network_interface| (network_interface.index, network_interface))
+            .collect();
+
+        Ok(())
+    }
+
+    fn apply(&mut self, message: &Message) -> Result<()> {
+        match message.kind {
+            RTM_NEWLINK => {
+                if let Some(network_interface) = parse_link(message)? {
+                    self.apply_new_link(network_interface);
+                }
+            }
+            RTM_DELLINK => {
+                if let Some(network_interface) = parse_link(message)? {
+                    if let Some(old) = self.network_interfaces.remove(&network_interface.index) {
+                        self.changes.push_back(InterfaceChange::Removed(old));
+                    }
+                }
+            }
+            RTM_NEWADDR => {
+                if let Some((index, addr)) = parse_address(message) {
+                    self.apply_new_address(index, addr);
+                }
+            }
+            RTM_DELADDR => {
+                if let Some((index, addr)) = parse_address(message) {
+                    self.apply_del_address(index, addr);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn apply_new_link(&mut self, mut network_interface: NetworkInterface) {
+        let index = network_interface.index;
+        let old = match self.network_interfaces.get_mut(&index) {
+            Some(old) => old,
+            None => {
+                self.changes
+                    .push_back(InterfaceChange::Added(network_interface.clone()));
+                self.network_interfaces.insert(index, network_interface);
+                return;
+            }
+        };
+
+        self.changes.extend(diff_link(old, &network_interface));
+
+        // link notifications carry no address
+        network_interface.addr = std::mem::take(&mut old.addr);
+        *old = network_interface;
+    }
+
+    fn apply_new_address(&mut self, index: u32, addr: Addr) {
+        let network_interface = match self.network_interfaces.get_mut(&index) {
+            Some(network_interface) => network_interface,
+            None => return,
+        };
+        let addr = adjust_address(network_interface, addr);
+
+        // the kernel notifies again addresses being updated, e.g. when the
+        // lifetimes of an IPv6 address are refreshed
+        let position = network_interface
+            .addr
+            .iter()
+            .position(|old| old.ip() == addr.ip() && old.netmask() == addr.netmask());
+
+        match position {
+            // refreshed lifetimes are not reported as a change
+            Some(position)
+                if network_interface.addr[position].without_lifetimes()
+                    == addr.without_lifetimes() =>
+            {
+                network_interface.addr[position] = addr;
+            }
+            Some(position) => {
+                let old = std::mem::replace(&mut network_interface.addr[position], addr);
+
+                self.changes
+                    .push_back(InterfaceChange::AddressRemoved { index, addr: old });
+                self.changes
+                    .push_back(InterfaceChange::AddressAdded { index, addr });
+            }
+            None => {
+                network_interface.addr.push(addr);
+                self.changes
+                    .push_back(InterfaceChange::AddressAdded { index, addr });
+            }
+        }
+    }
+
+    fn apply_del_address(&mut self, index: u32, addr: Addr) {
+        let network_interface = match self.network_interfaces.get_mut(&index) {
+            Some(network_interface) => network_interface,
+            None => return,
+        };
+        let position = network_interface
+            .addr
+            .iter()
+            .position(|old| old.ip() == addr.ip() && old.netmask() == addr.netmask());
+
+        if let Some(position) = position {
+            let addr = network_interface.addr.remove(position);
+
+            self.changes
+                .push_back(InterfaceChange::AddressRemoved { index, addr });
+        }
+    }
+}
+
+impl Iterator for Watcher {
+    type Item = Result<InterfaceChange>;
+
+    /// Waits for the next change, yielding `None` only if the watcher is in
+    /// non-blocking mode and no change is available
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_change().transpose()
+    }
+}
+
+impl AsRawFd for Watcher {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
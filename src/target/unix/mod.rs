@@ -0,0 +1,252 @@
+pub mod ffi;
+
+use std::collections::BTreeMap;
+use std::ffi::{CStr, OsStr, OsString};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::unix::ffi::OsStrExt;
+use std::slice::from_raw_parts;
+
+use libc::{AF_INET, AF_INET6, sockaddr_in, sockaddr_in6, AF_LINK, if_nametoindex};
+
+use crate::target::ffi::{lladdr, lladdr_len};
+use crate::target::getifaddrs;
+use crate::{
+    Addr, InterfaceKind, InterfaceQuery, LinkDetails, MacAddr, NetworkInterface,
+    NetworkInterfaceConfig, Result, V4IfAddr, V6IfAddr,
+};
+use crate::utils::{
+    ipv4_from_in_addr, ipv6_from_in6_addr, make_interface_flags, make_ipv4_netmask,
+    make_ipv6_netmask, split_broadcast_destination,
+};
+
+impl NetworkInterfaceConfig for NetworkInterface {
+    fn show() -> Result<Vec<NetworkInterface>> {
+        let mut network_interfaces: BTreeMap<u32, NetworkInterface> = BTreeMap::new();
+
+        for netifa in getifaddrs()? {
+            let netifa_addr = netifa.ifa_addr;
+            let netifa_family = if netifa_addr.is_null() {
+                continue;
+            } else {
+                unsafe { (*netifa_addr).sa_family as i32 }
+            };
+
+            let mut network_interface = match netifa_family {
+                AF_LINK => {
+                    let os_name = make_netifa_name(&netifa);
+                    let mac_addr = make_mac_addr(&netifa);
+                    let index = netifa_index(&netifa);
+                    NetworkInterface {
+                        name: os_name.to_string_lossy().into_owned(),
+                        os_name,
+                        mac_addr,
+                        addr: Vec::new(),
+                        index,
+                        flags: make_interface_flags(netifa.ifa_flags),
+                        kind: InterfaceKind::Unknown,
+                        link: LinkDetails::default(),
+                    }
+                }
+                AF_INET => {
+                    let socket_addr = netifa_addr as *mut sockaddr_in;
+                    let internet_address = unsafe { (*socket_addr).sin_addr };
+                    let os_name = make_netifa_name(&netifa);
+                    let index = netifa_index(&netifa);
+                    let flags = make_interface_flags(netifa.ifa_flags);
+                    let netmask = make_ipv4_netmask(&netifa);
+                    let ip = ipv4_from_in_addr(&internet_address)?;
+                    let (broadcast, destination) =
+                        split_broadcast_destination(flags, make_ipv4_dstaddr(&netifa)?);
+                    NetworkInterface {
+                        name: os_name.to_string_lossy().into_owned(),
+                        os_name,
+                        addr: vec![Addr::V4(V4IfAddr {
+                            ip,
+                            broadcast,
+                            destination,
+                            netmask,
+                            label: None,
+                        })],
+                        mac_addr: None,
+                        index,
+                        flags,
+                        kind: InterfaceKind::Unknown,
+                        link: LinkDetails::default(),
+                    }
+                }
+                AF_INET6 => {
+                    let socket_addr = netifa_addr as *mut sockaddr_in6;
+                    let internet_address = unsafe { (*socket_addr).sin6_addr };
+                    let os_name = make_netifa_name(&netifa);
+                    let index = netifa_index(&netifa);
+                    let flags = make_interface_flags(netifa.ifa_flags);
+                    let netmask = make_ipv6_netmask(&netifa);
+                    let (ip, scope_id) =
+                        make_ipv6_scoped_addr(ipv6_from_in6_addr(&internet_address)?, unsafe {
+                            (*socket_addr).sin6_scope_id
+                        });
+                    let (broadcast, destination) =
+                        split_broadcast_destination(flags, make_ipv6_dstaddr(&netifa)?);
+                    NetworkInterface {
+                        name: os_name.to_string_lossy().into_owned(),
+                        os_name,
+                        addr: vec![Addr::V6(V6IfAddr {
+                            ip,
+                            broadcast,
+                            destination,
+                            netmask,
+                            scope_id,
+                            flags: None,
+                            lifetimes: None,
+                        })],
+                        mac_addr: None,
+                        index,
+                        flags,
+                        kind: InterfaceKind::Unknown,
+                        link: LinkDetails::default(),
+                    }
+                }
+                _ => continue,
+            };
+
+            // the interface was removed while walking the list
+            if network_interface.index == 0 {
+                continue;
+            }
+
+            let is_link = netifa_family == AF_LINK;
+
+            network_interfaces
+                .entry(network_interface.index)
+                .and_modify(|old| {
+                    old.addr.append(&mut network_interface.addr);
+
+                    // the link record holds the name and hardware address of the
+                    // interface itself
+                    if is_link {
+                        old.name = network_interface.name.clone();
+                        old.os_name = network_interface.os_name.clone();
+                        old.mac_addr = network_interface.mac_addr;
+                    }
+                })
+                .or_insert(network_interface);
+        }
+
+        Ok(network_interfaces.into_values().collect())
+    }
+}
+
+/// Retrieves the network interfaces matching the provided query by filtering
+/// every network interface
+pub(crate) fn query(query: &InterfaceQuery) -> Result<Vec<NetworkInterface>> {
+    let mut network_interfaces = NetworkInterface::show()?;
+
+    query.retain(&mut network_interfaces);
+
+    Ok(network_interfaces)
+}
+
+/// Retrieves the network interface name, which is not required to be valid
+/// UTF-8 by the system
+fn make_netifa_name(netifa: &libc::ifaddrs) -> OsString {
+    let name = unsafe { CStr::from_ptr(netifa.ifa_name) };
+
+    OsStr::from_bytes(name.to_bytes()).to_os_string()
+}
+
+/// Retrieves the broadcast or destination address for the network interface
+/// provided of the AF_INET family.
+///
+/// ## References
+///
+/// https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/getifaddrs.3.html
+fn make_ipv4_dstaddr(netifa: &libc::ifaddrs) -> Result<Option<Ipv4Addr>> {
+    let ifa_dstaddr = netifa.ifa_dstaddr;
+
+    if ifa_dstaddr.is_null() {
+        return Ok(None);
+    }
+
+    let socket_addr = ifa_dstaddr as *mut sockaddr_in;
+    let internet_address = unsafe { (*socket_addr).sin_addr };
+    let addr = ipv4_from_in_addr(&internet_address)?;
+
+    Ok(Some(addr))
+}
+
+/// Retrieves the broadcast or destination address for the network interface
+/// provided of the AF_INET6 family.
+///
+/// ## References
+///
+/// https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/getifaddrs.3.html
+fn make_ipv6_dstaddr(netifa: &libc::ifaddrs) -> Result<Option<Ipv6Addr>> {
+    let ifa_dstaddr = netifa.ifa_dstaddr;
+
+    if ifa_dstaddr.is_null() {
+        return Ok(None);
+    }
+
+    let socket_addr = ifa_dstaddr as *mut sockaddr_in6;
+    let internet_address = unsafe { (*socket_addr).sin6_addr };
+    let addr = ipv6_from_in6_addr(&internet_address)?;
+
+    Ok(Some(addr))
+}
+
+/// Retrieves the scope id for an IPv6 address, removing the scope id that
+/// KAME derived network stacks embed on the second 16 bits word of
+/// link-local addresses.
+///
+/// ## References
+///
+/// <https://man.freebsd.org/cgi/man.cgi?query=inet6&sektion=4>
+fn make_ipv6_scoped_addr(ip: Ipv6Addr, sin6_scope_id: u32) -> (Ipv6Addr, u32) {
+    let mut segments = ip.segments();
+
+    if segments[0] & 0xffc0 != 0xfe80 || segments[1] == 0 {
+        return (ip, sin6_scope_id);
+    }
+
+    let embedded_scope_id = u32::from(segments[1]);
+    segments[1] = 0;
+
+    let scope_id = if sin6_scope_id == 0 {
+        embedded_scope_id
+    } else {
+        sin6_scope_id
+    };
+
+    (Ipv6Addr::from(segments), scope_id)
+}
+
+/// Retrieves the hardware address from the `sockaddr_dl` of an AF_LINK
+/// network interface
+///
+/// ## References
+///
+/// <https://man.freebsd.org/cgi/man.cgi?query=link_addr&sektion=3>
+fn make_mac_addr(netifa: &libc::ifaddrs) -> Option<MacAddr> {
+    let netifa_ptr = netifa as *const libc::ifaddrs as *mut _;
+    let len = unsafe { lladdr_len(netifa_ptr) } as usize;
+    let ptr = unsafe { lladdr(netifa_ptr) };
+
+    if ptr.is_null() {
+        return None;
+    }
+
+    let mac_slice = unsafe { from_raw_parts(ptr, len) };
+
+    MacAddr::new(mac_slice)
+}
+
+/// Retreives the name for the the network interface provided
+///
+/// ## References
+///
+/// https://man7.org/linux/man-pages/man3/if_nametoindex.3.html
+fn netifa_index(netifa: &libc::ifaddrs) -> u32 {
+    let name = netifa.ifa_name as *const libc::c_char;
+
+    unsafe { if_nametoindex(name) }
+}
@@ -8,3 +8,741 @@ fn show_network_interfaces() {
     println!("{network_interfaces:#?}");
     assert!(network_interfaces.len() > 1);
 }
+
+#[test]
+fn show_loopback_interface_flags() {
+    let network_interfaces = NetworkInterface::show().unwrap();
+
+    assert!(network_interfaces
+        .iter()
+        .any(|network_interface| network_interface.is_loopback() && network_interface.is_up()));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_network_interfaces_with_backends() {
+    use crate::{Addr, Backend, V6IfAddr};
+
+    // `getifaddrs` does not report the flags and lifetimes of IPv6 addresses
+    let without_flags = |addr: &Addr| match *addr {
+        Addr::V4(ifaddr_v4) => Addr::V4(ifaddr_v4),
+        Addr::V6(ifaddr_v6) => Addr::V6(V6IfAddr {
+            flags: None,
+            lifetimes: None,
+            ..ifaddr_v6
+        }),
+    };
+
+    let netlink = NetworkInterface::show_with_backend(Backend::Netlink).unwrap();
+    let getifaddrs = NetworkInterface::show_with_backend(Backend::GetIfAddrs).unwrap();
+
+    assert_eq!(netlink.len(), getifaddrs.len());
+
+    for network_interface in netlink {
+        let other = getifaddrs
+            .iter()
+            .find(|other| other.index == network_interface.index)
+            .unwrap();
+
+        assert_eq!(network_interface.name, other.name);
+        assert_eq!(network_interface.mac_addr, other.mac_addr);
+        assert_eq!(network_interface.flags, other.flags);
+        assert_eq!(network_interface.addr.len(), other.addr.len());
+
+        for addr in network_interface.addr.iter() {
+            assert!(other.addr.contains(&without_flags(addr)));
+        }
+    }
+}
+
+#[test]
+fn show_link_local_addresses_scope_id() {
+    use crate::{Addr, AddrScope};
+
+    for network_interface in NetworkInterface::show().unwrap() {
+        for addr in network_interface.addr.iter() {
+            if let Addr::V6(ifaddr_v6) = addr {
+                if ifaddr_v6.scope() == AddrScope::Link {
+                    assert_eq!(ifaddr_v6.scope_id, network_interface.index);
+                    assert_eq!(ifaddr_v6.socket_addr(0).scope_id(), network_interface.index);
+                }
+            }
+        }
+    }
+}
+
+/// Runs `f` on a thread moved into a new network namespace, so interfaces
+/// can be created and removed without affecting the system.
+///
+/// Returns `None` if the process is not allowed to create network namespaces.
+#[cfg(all(test, any(target_os = "android", target_os = "linux")))]
+pub(crate) fn in_network_namespace<F, T>(f: F) -> Option<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    std::thread::spawn(move || {
+        if unsafe { libc::unshare(libc::CLONE_NEWNET) } != 0 {
+            println!(
+                "skipping, unable to create a network namespace: {}",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+
+        Some(f())
+    })
+    .join()
+    .unwrap()
+}
+
+/// Runs `ip` with the provided arguments, the child process inherits the
+/// network namespace of the calling thread
+#[cfg(all(test, any(target_os = "android", target_os = "linux")))]
+fn ip(args: &str) {
+    let status = std::process::Command::new("ip")
+        .args(args.split_whitespace())
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "ip {}", args);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn watch_network_interface_changes() {
+    use crate::{Addr, InterfaceChange};
+    use std::time::{Duration, Instant};
+
+    in_network_namespace(|| {
+        let mut watcher = NetworkInterface::watch().unwrap();
+        let mut changes = Vec::new();
+
+        watcher.set_nonblocking(true).unwrap();
+
+        // TAP devices support changing their hardware address unlike IFBs
+        ip("tuntap add nitest0 mode tap");
+        ip("link set nitest0 name nitest1");
+        ip("link set nitest1 address 02:fc:00:00:00:09");
+        ip("link set nitest1 up");
+        ip("addr add 192.0.2.1/24 dev nitest1");
+        ip("addr del 192.0.2.1/24 dev nitest1");
+        ip("link del nitest1");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while !matches!(changes.last(), Some(InterfaceChange::Removed(_))) {
+            assert!(Instant::now() < deadline, "{:#?}", changes);
+            changes.extend(watcher.drain().unwrap());
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let index = match &changes[0] {
+            InterfaceChange::Added(network_interface) => {
+                assert_eq!(network_interface.name, "nitest0");
+                network_interface.index
+            }
+            change => panic!("unexpected change {:?}", change),
+        };
+        let has_address = |change: &InterfaceChange| match change {
+            InterfaceChange::AddressAdded {
+                addr: Addr::V4(ifaddr_v4),
+                ..
+            }
+            | InterfaceChange::AddressRemoved {
+                addr: Addr::V4(ifaddr_v4),
+                ..
+            } => ifaddr_v4.to_string() == "192.0.2.1/24",
+            _ => false,
+        };
+
+        assert!(changes.iter().all(|change| change.index() == index));
+        assert!(changes.iter().any(|change| matches!(
+            change,
+            InterfaceChange::Renamed { new_name, .. } if new_name == "nitest1"
+        )));
+        assert!(changes.iter().any(|change| matches!(
+            change,
+            InterfaceChange::MacChanged { new: Some(new), .. } if new.to_string() == "02:fc:00:00:00:09"
+        )));
+        assert!(changes.iter().any(|change| matches!(
+            change,
+            InterfaceChange::FlagsChanged { old, new, .. } if !old.is_up() && new.is_up()
+        )));
+        assert_eq!(
+            changes.iter().filter(|change| has_address(change)).count(),
+            2
+        );
+        assert!(watcher
+            .network_interfaces()
+            .iter()
+            .all(|network_interface| network_interface.index != index));
+    });
+}
+
+#[test]
+#[cfg(all(feature = "tokio", any(target_os = "android", target_os = "linux")))]
+fn stream_network_interface_changes() {
+    use crate::InterfaceChange;
+    use futures_core::Stream;
+    use std::pin::Pin;
+
+    in_network_namespace(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let network_interfaces = NetworkInterface::show_async().await.unwrap();
+            let mut stream = NetworkInterface::watch_async().await.unwrap();
+            let mut changes = Vec::new();
+
+            assert_eq!(network_interfaces.len(), 1);
+            assert!(network_interfaces[0].is_loopback());
+
+            ip("link add nitest0 type ifb");
+            ip("link del nitest0");
+
+            while !matches!(changes.last(), Some(InterfaceChange::Removed(_))) {
+                let change = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+
+                changes.push(change.unwrap().unwrap());
+            }
+
+            assert!(matches!(
+                &changes[0],
+                InterfaceChange::Added(network_interface) if network_interface.name == "nitest0"
+            ));
+            assert_eq!(stream.watcher().network_interfaces().len(), 1);
+        });
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_loopback_interface_stats() {
+    use std::net::UdpSocket;
+
+    let loopback = NetworkInterface::show()
+        .unwrap()
+        .into_iter()
+        .find(|network_interface| network_interface.is_loopback())
+        .unwrap();
+    let before = loopback.stats().unwrap().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    socket
+        .send_to(b"ping", socket.local_addr().unwrap())
+        .unwrap();
+
+    let after = loopback.stats().unwrap().unwrap();
+
+    assert!(after.tx_packets > before.tx_packets);
+    assert!(after.rx_bytes > before.rx_bytes);
+}
+
+#[test]
+fn lookup_network_interfaces() {
+    for network_interface in NetworkInterface::show().unwrap() {
+        let mut lookups = vec![
+            NetworkInterface::by_name(&network_interface.name).unwrap(),
+            NetworkInterface::by_index(network_interface.index).unwrap(),
+        ];
+
+        if let Some(mac_addr) = &network_interface.mac_addr {
+            // several interfaces may share a hardware address, such as the
+            // loopback and tunnels
+            if let Some(other) = NetworkInterface::by_mac(mac_addr).unwrap() {
+                assert_eq!(other.mac_addr.as_ref(), Some(mac_addr));
+            }
+        }
+
+        for addr in network_interface.addr.iter() {
+            lookups.push(NetworkInterface::by_ip(addr.ip()).unwrap());
+        }
+
+        for lookup in lookups {
+            let lookup = lookup.unwrap();
+
+            assert_eq!(lookup.name, network_interface.name);
+            assert_eq!(lookup.index, network_interface.index);
+            assert_eq!(lookup.addr.len(), network_interface.addr.len());
+
+            for addr in lookup.addr.iter() {
+                assert!(network_interface
+                    .addr
+                    .iter()
+                    .any(|other| other.without_lifetimes() == addr.without_lifetimes()));
+            }
+        }
+    }
+
+    assert_eq!(NetworkInterface::by_name("nitest-missing").unwrap(), None);
+    assert_eq!(NetworkInterface::by_name("").unwrap(), None);
+    assert_eq!(NetworkInterface::by_index(u32::MAX).unwrap(), None);
+    assert_eq!(
+        NetworkInterface::by_ip("198.51.100.1".parse().unwrap()).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn show_network_interfaces_sorted_by_index() {
+    let network_interfaces = NetworkInterface::show().unwrap();
+
+    assert!(network_interfaces
+        .windows(2)
+        .all(|pair| pair[0].index < pair[1].index));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn group_labeled_addresses_by_index() {
+    use crate::{Addr, Backend};
+
+    in_network_namespace(|| {
+        ip("link add nitest0 type ifb");
+        ip("addr add 192.0.2.1/24 dev nitest0");
+        ip("addr add 192.0.2.2/24 dev nitest0 label nitest0:1");
+
+        for backend in [Backend::Netlink, Backend::GetIfAddrs] {
+            let network_interfaces = NetworkInterface::show_with_backend(backend).unwrap();
+
+            assert_eq!(network_interfaces.len(), 2, "{:?}", backend);
+            assert!(network_interfaces[0].is_loopback());
+            assert_eq!(network_interfaces[1].name, "nitest0");
+            assert_eq!(network_interfaces[1].addr.len(), 2);
+            assert!(network_interfaces[1].mac_addr.is_some());
+
+            let mut labels = network_interfaces[1]
+                .addr
+                .iter()
+                .map(|addr| match addr {
+                    Addr::V4(ifaddr_v4) => ifaddr_v4.label.map(|label| label.to_string()),
+                    Addr::V6(_) => None,
+                })
+                .collect::<Vec<_>>();
+
+            labels.sort();
+
+            assert_eq!(
+                labels,
+                [None, Some(String::from("nitest0:1"))],
+                "{:?}",
+                backend
+            );
+        }
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_ipv6_address_flags() {
+    use std::time::Duration;
+
+    use crate::{Addr, AddrFlags, Backend};
+
+    in_network_namespace(|| {
+        ip("link add nitest0 type veth peer name nitest1");
+        ip("link set nitest0 up");
+        ip("link set nitest1 up");
+        // duplicate address detection lasts for a second
+        ip("addr add 2001:db8::1/64 dev nitest0");
+        ip("addr add 2001:db8::2/64 dev nitest0 nodad noprefixroute preferred_lft 0 valid_lft 600");
+
+        let network_interface = NetworkInterface::show_with_backend(Backend::Netlink)
+            .unwrap()
+            .into_iter()
+            .find(|network_interface| network_interface.name == "nitest0")
+            .unwrap();
+        let ifaddr_v6 = |ip: &str| {
+            network_interface
+                .addr
+                .iter()
+                .find_map(|addr| match addr {
+                    Addr::V6(ifaddr_v6) if ifaddr_v6.ip.to_string() == ip => Some(*ifaddr_v6),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let tentative = ifaddr_v6("2001:db8::1");
+        let deprecated = ifaddr_v6("2001:db8::2");
+
+        assert!(tentative.flags.unwrap().is_tentative());
+        assert!(tentative.flags.unwrap().contains(AddrFlags::PERMANENT));
+        assert!(!tentative.is_preferred());
+        assert_eq!(tentative.lifetimes.unwrap().valid, None);
+
+        let flags = deprecated.flags.unwrap();
+        let lifetimes = deprecated.lifetimes.unwrap();
+
+        assert!(flags.is_deprecated() && !flags.is_tentative());
+        assert!(flags.contains(AddrFlags::NODAD | AddrFlags::NOPREFIXROUTE));
+        assert!(!deprecated.is_preferred());
+        assert_eq!(lifetimes.preferred, Some(Duration::ZERO));
+        assert!(lifetimes.valid.unwrap() <= Duration::from_secs(600));
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn query_network_interfaces() {
+    use crate::{Family, InterfaceQuery};
+
+    in_network_namespace(|| {
+        ip("link set lo up");
+        ip("link add nitest0 type ifb");
+        ip("link add nitest1 type ifb");
+        ip("link add other0 type ifb");
+        ip("link set nitest0 up");
+        ip("link set other0 up");
+        ip("addr add 192.0.2.1/24 dev nitest0");
+        ip("addr add 2001:db8::1/64 dev nitest0 nodad");
+        ip("addr add 192.0.2.2/24 dev nitest1");
+        ip("addr add 192.0.2.3/24 dev other0");
+
+        let network_interfaces = InterfaceQuery::new()
+            .family(Family::V4)
+            .up_only()
+            .exclude_loopback()
+            .name_glob("nitest*")
+            .run()
+            .unwrap();
+
+        assert_eq!(network_interfaces.len(), 1);
+        assert_eq!(network_interfaces[0].name, "nitest0");
+        assert_eq!(network_interfaces[0].addr.len(), 1);
+        assert_eq!(network_interfaces[0].addr[0].ip().to_string(), "192.0.2.1");
+
+        let network_interfaces = InterfaceQuery::new().family(Family::V6).run().unwrap();
+
+        assert!(network_interfaces
+            .iter()
+            .all(|network_interface| network_interface
+                .addr
+                .iter()
+                .all(|addr| addr.ip().is_ipv6())));
+        assert!(network_interfaces
+            .iter()
+            .any(|network_interface| network_interface.name == "nitest0"));
+        assert!(!network_interfaces
+            .iter()
+            .any(|network_interface| network_interface.name == "nitest1"));
+        assert_eq!(
+            InterfaceQuery::new().run().unwrap(),
+            NetworkInterface::show().unwrap()
+        );
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_routes() {
+    use crate::{routes, RouteKind, RouteProtocol, LOCAL_TABLE, MAIN_TABLE};
+
+    in_network_namespace(|| {
+        ip("link set lo up");
+        ip("link add nitest0 type ifb");
+        ip("link add nitest1 type ifb");
+        ip("link set nitest0 up");
+        ip("link set nitest1 up");
+        ip("addr add 192.0.2.1/24 dev nitest0");
+        ip("addr add 198.51.100.1/24 dev nitest1");
+        ip("route add default via 192.0.2.254 metric 100");
+        ip("route add 203.0.113.0/24 proto static src 192.0.2.1 nexthop via 192.0.2.253 nexthop via 198.51.100.254");
+        ip("route add unreachable 10.0.0.0/8");
+
+        let nitest0 = NetworkInterface::by_name("nitest0").unwrap().unwrap();
+        let nitest1 = NetworkInterface::by_name("nitest1").unwrap().unwrap();
+        let routes = routes().unwrap();
+
+        let default = routes
+            .iter()
+            .find(|route| route.is_default() && route.destination.is_ipv4())
+            .unwrap();
+
+        assert_eq!(default.gateway, Some("192.0.2.254".parse().unwrap()));
+        assert_eq!(default.metric, 100);
+        assert_eq!(default.table, MAIN_TABLE);
+        assert_eq!(default.network_interface().unwrap(), Some(nitest0.clone()));
+
+        let multipath = routes
+            .iter()
+            .filter(|route| route.destination.to_string() == "203.0.113.0")
+            .collect::<Vec<_>>();
+
+        assert_eq!(multipath.len(), 2);
+        assert_eq!(multipath[0].index, nitest0.index);
+        assert_eq!(multipath[1].index, nitest1.index);
+        assert!(multipath.iter().all(|route| route.prefix_len == 24
+            && route.protocol == RouteProtocol::Static
+            && route.source == Some("192.0.2.1".parse().unwrap())));
+
+        let unreachable = routes
+            .iter()
+            .find(|route| route.kind == RouteKind::Unreachable)
+            .unwrap();
+
+        assert_eq!(unreachable.destination.to_string(), "10.0.0.0");
+        assert_eq!(unreachable.network_interface().unwrap(), None);
+        assert!(routes.iter().any(|route| route.table == LOCAL_TABLE
+            && route.kind == RouteKind::Local
+            && route.destination.to_string() == "192.0.2.1"));
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_default_gateways() {
+    use crate::{default_gateways, default_route_interface, Family};
+
+    in_network_namespace(|| {
+        ip("link set lo up");
+
+        assert!(default_gateways().unwrap().is_empty());
+        assert_eq!(default_route_interface(Family::V4).unwrap(), None);
+
+        ip("link add nitest0 type ifb");
+        ip("link add nitest1 type ifb");
+        ip("link set nitest0 up");
+        ip("link set nitest1 up");
+        ip("addr add 192.0.2.1/24 dev nitest0");
+        ip("addr add 198.51.100.1/24 dev nitest1");
+        ip("addr add 2001:db8::1/64 dev nitest1 nodad");
+        ip("route add default via 192.0.2.254 metric 200");
+        ip("route add default via 198.51.100.254 metric 100");
+        ip("-6 route add default dev nitest1 metric 50");
+
+        let nitest0 = NetworkInterface::by_name("nitest0").unwrap().unwrap();
+        let nitest1 = NetworkInterface::by_name("nitest1").unwrap().unwrap();
+        let default_gateways = default_gateways().unwrap();
+
+        assert_eq!(default_gateways.len(), 3);
+        assert_eq!(default_gateways[0].family, Family::V6);
+        assert_eq!(default_gateways[0].gateway, None);
+        assert_eq!(default_gateways[0].metric, 50);
+        assert_eq!(default_gateways[0].network_interface, nitest1);
+        assert_eq!(
+            default_gateways[1].gateway,
+            Some("198.51.100.254".parse().unwrap())
+        );
+        assert_eq!(default_gateways[1].metric, 100);
+        assert_eq!(
+            default_gateways[2].gateway,
+            Some("192.0.2.254".parse().unwrap())
+        );
+        assert_eq!(default_gateways[2].network_interface, nitest0);
+        assert_eq!(
+            default_route_interface(Family::V4).unwrap(),
+            Some(nitest1.clone())
+        );
+        assert_eq!(default_route_interface(Family::V6).unwrap(), Some(nitest1));
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_neighbors() {
+    use crate::{neighbors, NeighborState};
+
+    in_network_namespace(|| {
+        ip("link add nitest0 type ifb");
+        ip("link set nitest0 up");
+        ip("addr add 192.0.2.1/24 dev nitest0");
+        ip("neigh add 192.0.2.10 lladdr 02:00:00:00:00:01 dev nitest0 nud permanent");
+        ip("neigh add 192.0.2.11 lladdr 02:00:00:00:00:02 dev nitest0 nud stale");
+        ip("-6 neigh add 2001:db8::2 lladdr 02:00:00:00:00:03 dev nitest0 router");
+
+        let nitest0 = NetworkInterface::by_name("nitest0").unwrap().unwrap();
+        let neighbors = neighbors().unwrap();
+        let neighbor = |ip: &str| {
+            neighbors
+                .iter()
+                .find(|neighbor| neighbor.ip.to_string() == ip)
+                .unwrap()
+        };
+
+        assert_eq!(neighbor("192.0.2.10").state, NeighborState::Permanent);
+        assert_eq!(
+            neighbor("192.0.2.10").mac_addr,
+            Some("02:00:00:00:00:01".parse().unwrap())
+        );
+        assert_eq!(
+            neighbor("192.0.2.10").network_interface().unwrap(),
+            Some(nitest0)
+        );
+        assert_eq!(neighbor("192.0.2.11").state, NeighborState::Stale);
+        assert!(!neighbor("192.0.2.11").flags.is_router());
+        assert!(neighbor("2001:db8::2").flags.is_router());
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_network_interface_kinds() {
+    use crate::{InterfaceKind, MacvlanMode};
+
+    in_network_namespace(|| {
+        ip("link add nitest0 type veth peer name nitest1");
+        ip("link add nimv0 link nitest0 type macvlan mode private");
+        ip("link add nitest2 type ifb");
+        ip("tuntap add nitun0 mode tun");
+        ip("tuntap add nitap0 mode tap");
+
+        let kind = |name: &str| NetworkInterface::by_name(name).unwrap().unwrap().kind;
+
+        assert_eq!(kind("lo"), InterfaceKind::Loopback);
+        assert_eq!(kind("nitest0"), InterfaceKind::Veth);
+        assert_eq!(
+            kind("nimv0"),
+            InterfaceKind::Macvlan {
+                mode: MacvlanMode::Private
+            }
+        );
+        assert_eq!(kind("nitest2"), InterfaceKind::Other(String::from("ifb")));
+        assert_eq!(kind("nitun0"), InterfaceKind::Tun);
+        assert_eq!(kind("nitap0"), InterfaceKind::Tap);
+        assert!(kind("nitap0").is_virtual());
+        assert!(!kind("lo").is_virtual());
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_network_interface_link_state() {
+    use crate::{Backend, OperState};
+
+    in_network_namespace(|| {
+        let show = || {
+            NetworkInterface::show_with_backend(Backend::Netlink)
+                .unwrap()
+                .into_iter()
+                .find(|network_interface| network_interface.name == "nitest0")
+                .unwrap()
+        };
+
+        ip("link add nitest0 type veth peer name nitest1");
+        ip("link set nitest0 up");
+
+        // the peer being down is seen as an unplugged cable
+        let unplugged = show();
+
+        assert!(unplugged.is_up());
+        assert!(!unplugged.is_oper_up());
+        assert_eq!(unplugged.link.operstate, Some(OperState::LowerLayerDown));
+        assert_eq!(unplugged.link.carrier, Some(false));
+        assert_eq!(unplugged.link.mtu, Some(1500));
+        assert_eq!(unplugged.link.link_type, Some(1));
+
+        ip("link set nitest1 up");
+
+        let plugged = show();
+
+        assert!(plugged.is_oper_up());
+        assert_eq!(plugged.link.operstate, Some(OperState::Up));
+        assert_eq!(plugged.link.carrier, Some(true));
+        assert!(plugged.link.carrier_up_count > unplugged.link.carrier_up_count);
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn enrich_network_interfaces_from_sysfs() {
+    use crate::Sysfs;
+
+    let mut network_interfaces = NetworkInterface::show().unwrap();
+
+    Sysfs::new().enrich_all(&mut network_interfaces);
+
+    let loopback = network_interfaces
+        .iter()
+        .find(|network_interface| network_interface.is_loopback())
+        .unwrap();
+
+    assert!(loopback.link.mtu.is_some());
+    assert!(loopback.link.operstate.is_some());
+    assert_eq!(loopback.link.link_type, Some(772));
+    assert_eq!(loopback.link.device, None);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_network_interfaces_in_netns() {
+    use crate::Error;
+
+    // `ip netns` creates the namespace, this only skips the test if the
+    // process is not allowed to create network namespaces
+    in_network_namespace(|| {
+        let netns = format!("nitest{}", std::process::id());
+
+        ip(&format!("netns add {}", netns));
+        ip(&format!("-n {} link add nitest0 type ifb", netns));
+
+        let network_interfaces =
+            NetworkInterface::show_in_netns(format!("/var/run/netns/{}", netns));
+
+        ip(&format!("netns del {}", netns));
+
+        let names = network_interfaces
+            .unwrap()
+            .into_iter()
+            .map(|network_interface| network_interface.name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["lo", "nitest0"]);
+        assert!(NetworkInterface::by_name("nitest0").unwrap().is_none());
+        assert!(matches!(
+            NetworkInterface::show_in_netns("/var/run/netns/nitest-missing"),
+            Err(Error::ReadFileError { .. })
+        ));
+        assert!(matches!(
+            NetworkInterface::show_in_netns("/proc/self/ns/uts"),
+            Err(ref error @ Error::SetNsError { .. }) if error.raw_os_error() == Some(libc::EINVAL)
+        ));
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn show_non_utf8_interface_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::process::Command;
+
+    use crate::Backend;
+
+    in_network_namespace(|| {
+        let os_name = OsStr::from_bytes(b"nitest\xff");
+
+        // the `ip` helper only takes UTF-8 arguments
+        let status = Command::new("ip")
+            .args(["link", "add"])
+            .arg(os_name)
+            .args(["type", "ifb"])
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+
+        let status = Command::new("ip")
+            .args(["addr", "add", "192.0.2.1/24", "dev"])
+            .arg(os_name)
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+
+        for backend in [Backend::Netlink, Backend::GetIfAddrs] {
+            let network_interfaces = NetworkInterface::show_with_backend(backend).unwrap();
+            let network_interface = network_interfaces
+                .iter()
+                .find(|network_interface| network_interface.os_name == os_name)
+                .unwrap();
+
+            assert_eq!(network_interface.name, "nitest\u{fffd}", "{:?}", backend);
+            assert_eq!(network_interface.addr.len(), 1, "{:?}", backend);
+        }
+    });
+}
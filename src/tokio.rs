@@ -0,0 +1,122 @@
+//! Asynchronous counterparts of the network interface retrieval and change
+//! watching APIs for the `tokio` runtime, enabled by the `tokio` feature.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::collections::VecDeque;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::pin::Pin;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::task::{Context, Poll};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use ::tokio::io::unix::AsyncFd;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::{Error, InterfaceChange, Watcher};
+use crate::{NetworkInterface, NetworkInterfaceConfig, Result};
+
+impl NetworkInterface {
+    /// List system's network interfaces configuration without blocking the
+    /// runtime, the retrieval takes place on the blocking thread pool.
+    pub async fn show_async() -> Result<Vec<NetworkInterface>> {
+        run_blocking(NetworkInterface::show).await
+    }
+
+    /// Watches the system's network interfaces for changes, reporting them
+    /// through an `InterfaceChangeStream`.
+    ///
+    /// Must be called from within a `tokio` runtime with IO enabled.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub async fn watch_async() -> Result<InterfaceChangeStream> {
+        let watcher = run_blocking(NetworkInterface::watch).await?;
+
+        InterfaceChangeStream::new(watcher)
+    }
+}
+
+/// Runs `f` on the blocking thread pool, resuming its panic if any
+async fn run_blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match ::tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+/// Asynchronous `Stream` of the changes on the system's network interfaces,
+/// driven by a `Watcher` registered on the `tokio` reactor.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub struct InterfaceChangeStream {
+    watcher: AsyncFd<Watcher>,
+    changes: VecDeque<InterfaceChange>,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl InterfaceChangeStream {
+    /// Registers the provided `Watcher` on the `tokio` reactor, moving it into
+    /// non-blocking mode.
+    ///
+    /// Must be called from within a `tokio` runtime with IO enabled.
+    pub fn new(watcher: Watcher) -> Result<Self> {
+        watcher.set_nonblocking(true)?;
+
+        let watcher = AsyncFd::new(watcher).map_err(|error| make_io_error("epoll", error))?;
+
+        Ok(InterfaceChangeStream {
+            watcher,
+            changes: VecDeque::new(),
+        })
+    }
+
+    /// Retrieves the underlying `Watcher`
+    pub fn watcher(&self) -> &Watcher {
+        self.watcher.get_ref()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl futures_core::Stream for InterfaceChangeStream {
+    type Item = Result<InterfaceChange>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(change) = this.changes.pop_front() {
+                return Poll::Ready(Some(Ok(change)));
+            }
+
+            let mut guard = match this.watcher.poll_read_ready_mut(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(error)) => {
+                    return Poll::Ready(Some(Err(make_io_error("epoll", error))))
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            // `Watcher::drain` reads until the socket would block, so the
+            // readiness is cleared even if no change was reported
+            let changes = guard.get_inner_mut().drain();
+
+            guard.clear_ready();
+
+            match changes {
+                Ok(changes) => this.changes.extend(changes),
+                Err(error) => return Poll::Ready(Some(Err(error))),
+            }
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn make_io_error(operation: &'static str, error: std::io::Error) -> Error {
+    // the registration on the reactor does not involve the netlink socket
+    // itself, so no other backend could be used instead
+    Error::OsError {
+        operation,
+        interface: None,
+        source: error,
+    }
+}
@@ -1,9 +1,21 @@
 use std::net::{Ipv4Addr, Ipv6Addr};
 use libc::{in6_addr, in_addr, sockaddr_in, sockaddr_in6};
 
-use crate::Result;
+use crate::{InterfaceFlags, Result};
 use crate::interface::Netmask;
 
+/// Pairs of `IFF_*` flags with their `InterfaceFlags` counterpart
+const IFF_FLAGS: [(libc::c_int, InterfaceFlags); 8] = [
+    (libc::IFF_UP, InterfaceFlags::UP),
+    (libc::IFF_BROADCAST, InterfaceFlags::BROADCAST),
+    (libc::IFF_LOOPBACK, InterfaceFlags::LOOPBACK),
+    (libc::IFF_POINTOPOINT, InterfaceFlags::POINT_TO_POINT),
+    (libc::IFF_RUNNING, InterfaceFlags::RUNNING),
+    (libc::IFF_NOARP, InterfaceFlags::NOARP),
+    (libc::IFF_PROMISC, InterfaceFlags::PROMISC),
+    (libc::IFF_MULTICAST, InterfaceFlags::MULTICAST),
+];
+
 /// Creates a `Ipv4Addr` from a (Unix) `in_addr` taking in account
 /// the CPU endianess to avoid having twisted IP addresses.
 ///
@@ -55,10 +67,46 @@ pub fn make_ipv6_netmask(netifa: &libc::ifaddrs) -> Netmask<Ipv6Addr> {
     let socket_addr = sockaddr as *mut sockaddr_in6;
     let internet_address = unsafe { (*socket_addr).sin6_addr };
 
-    //  Ignore local addresses
-    if internet_address.s6_addr[0] == 0xfe && internet_address.s6_addr[1] == 0x80 {
-        return None;
+    ipv6_from_in6_addr(&internet_address).ok()
+}
+
+/// Creates `InterfaceFlags` from the `IFF_*` flags of a network interface, as
+/// found on `ifa_flags` for instance.
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man7/netdevice.7.html>
+pub fn make_interface_flags(ifa_flags: libc::c_uint) -> InterfaceFlags {
+    let mut flags = InterfaceFlags::empty();
+
+    for (iff, flag) in IFF_FLAGS.iter() {
+        if ifa_flags & (*iff as libc::c_uint) != 0 {
+            flags.insert(*flag);
+        }
     }
 
-    ipv6_from_in6_addr(&internet_address).ok()
+    flags
+}
+
+/// Splits the address found on the `ifa_ifu` union of a `ifaddrs` instance,
+/// also known as `ifa_broadaddr` and `ifa_dstaddr`, into the broadcast and
+/// destination addresses based on the interface flags.
+///
+/// The address is a broadcast address only if `IFF_BROADCAST` is set, and a
+/// destination address only if `IFF_POINTOPOINT` is set.
+///
+/// ## References
+///
+/// <https://man7.org/linux/man-pages/man3/getifaddrs.3.html>
+pub fn split_broadcast_destination<T>(
+    flags: InterfaceFlags,
+    dstaddr: Option<T>,
+) -> (Option<T>, Option<T>) {
+    if flags.is_point_to_point() {
+        (None, dstaddr)
+    } else if flags.is_broadcast() {
+        (dstaddr, None)
+    } else {
+        (None, None)
+    }
 }